@@ -0,0 +1,73 @@
+use anyhow::{Context, Result};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Entry {
+    fetched_at: u64,
+    value: serde_json::Value,
+}
+
+fn cache_path(namespace: &str) -> Result<PathBuf> {
+    let cache_dir = dirs::cache_dir()
+        .context("Could not find cache directory")?
+        .join("rofi-linear");
+
+    if !cache_dir.exists() {
+        fs::create_dir_all(&cache_dir)?;
+    }
+
+    Ok(cache_dir.join(format!("{}.json", namespace)))
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+fn load(namespace: &str) -> Result<HashMap<String, Entry>> {
+    let path = cache_path(namespace)?;
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    // A corrupt cache is just a cold cache
+    Ok(serde_json::from_str(&content).unwrap_or_default())
+}
+
+/// Look up a cached value that is younger than `max_age`
+pub fn get<T: DeserializeOwned>(namespace: &str, key: &str, max_age: Duration) -> Option<T> {
+    let entries = load(namespace).ok()?;
+    let entry = entries.get(key)?;
+
+    if now().saturating_sub(entry.fetched_at) > max_age.as_secs() {
+        return None;
+    }
+
+    serde_json::from_value(entry.value.clone()).ok()
+}
+
+/// Store a value, dropping entries older than `max_age` from the namespace
+pub fn set<T: Serialize>(namespace: &str, key: &str, value: &T, max_age: Duration) -> Result<()> {
+    let mut entries = load(namespace)?;
+    let now = now();
+
+    entries.retain(|_, e| now.saturating_sub(e.fetched_at) <= max_age.as_secs());
+    entries.insert(
+        key.to_string(),
+        Entry {
+            fetched_at: now,
+            value: serde_json::to_value(value)?,
+        },
+    );
+
+    let content = serde_json::to_string(&entries)?;
+    fs::write(cache_path(namespace)?, content)?;
+    Ok(())
+}
@@ -0,0 +1,29 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Copy text to the clipboard with wl-copy on Wayland, xclip otherwise
+pub fn copy(text: &str) -> Result<()> {
+    let (program, args): (&str, &[&str]) = if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        ("wl-copy", &[])
+    } else {
+        ("xclip", &["-selection", "clipboard"])
+    };
+
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", program))?;
+
+    child
+        .stdin
+        .as_mut()
+        .context("Failed to get stdin")?
+        .write_all(text.as_bytes())?;
+
+    child
+        .wait()
+        .with_context(|| format!("Failed to wait for {}", program))?;
+    Ok(())
+}
@@ -0,0 +1,142 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::cache;
+use crate::commands::issues;
+use crate::config;
+use crate::frecency;
+use crate::launcher::Menu;
+use crate::linear::{IssueSummary, IssueUpdate, LinearClient, User, Viewer};
+use crate::rofi;
+
+/// Team membership rarely changes, so the picker can skip the network
+pub const MEMBERS_TTL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Fetch the authenticated user, cached
+pub async fn viewer(client: &LinearClient) -> Result<Viewer> {
+    if let Some(viewer) = cache::get("viewer", "viewer", MEMBERS_TTL) {
+        return Ok(viewer);
+    }
+
+    let viewer = client.get_viewer().await?;
+    cache::set("viewer", "viewer", &viewer, MEMBERS_TTL).ok();
+    Ok(viewer)
+}
+
+/// Fetch a team's members, cached
+pub async fn members(client: &LinearClient, team_id: &str) -> Result<Vec<User>> {
+    if let Some(members) = cache::get("members", team_id, MEMBERS_TTL) {
+        return Ok(members);
+    }
+
+    let members = client.get_team_members(team_id).await?;
+    cache::set("members", team_id, &members, MEMBERS_TTL).ok();
+    Ok(members)
+}
+
+/// Order members for a picker: the viewer first, then the most frecent
+/// assignees, then everyone else alphabetically
+pub fn sort_members(members: &mut [User], viewer: &Viewer) {
+    members.sort_by_key(|m| m.name.to_lowercase());
+    frecency::sort("assignees", members, |m| &m.id);
+    members.sort_by_key(|m| m.id != viewer.id);
+}
+
+/// Pick from `options`, whose first rows are `members`, highlighting the
+/// most frecent assignee and recording the pick
+pub fn select_member(prompt: &str, options: &[String], members: &[User]) -> Result<Option<usize>> {
+    let menu = Menu {
+        id: Some("assign"),
+        prompt,
+        options,
+        selected: frecency::best("assignees", members, |m| &m.id),
+        ..Default::default()
+    };
+    let idx = rofi::menu(&menu)?.map(|s| s.index());
+    if let Some(member) = idx.and_then(|idx| members.get(idx)) {
+        frecency::record("assignees", &member.id).ok();
+    }
+    Ok(idx)
+}
+
+/// Let the user pick an assignee for an issue
+///
+/// Returns `Some(None)` when "Unassign" is picked and `None` on cancel.
+pub async fn pick_assignee(
+    client: &LinearClient,
+    issue: &IssueSummary,
+) -> Result<Option<Option<User>>> {
+    let viewer = viewer(client).await?;
+    let mut members = members(client, &issue.team.id).await?;
+    sort_members(&mut members, &viewer);
+
+    let current = issue.assignee.as_ref().map(|a| a.id.as_str());
+    let mut options: Vec<String> = members
+        .iter()
+        .map(|m| {
+            let mut row = m.name.clone();
+            if m.id == viewer.id {
+                row.push_str(" (me)");
+            }
+            if Some(m.id.as_str()) == current {
+                row.push_str(" (current)");
+            }
+            row
+        })
+        .collect();
+    options.push("Unassign".to_string());
+
+    let prompt = format!("Assign {}", issue.identifier);
+    Ok(match select_member(&prompt, &options, &members)? {
+        Some(idx) if idx < members.len() => Some(Some(members[idx].clone())),
+        Some(_) => Some(None),
+        None => None,
+    })
+}
+
+pub async fn run(identifier: Option<String>) -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let client = LinearClient::new(&api_key);
+    match issues::pick(&client, identifier.as_deref()).await? {
+        Some(issue) => apply(&client, &issue).await.map(|_| ()),
+        None => Ok(()), // User cancelled
+    }
+}
+
+/// Pick a new assignee for an issue and apply it
+///
+/// Returns whether the issue was updated, `false` when the picker was cancelled.
+pub async fn apply(client: &LinearClient, issue: &IssueSummary) -> Result<bool> {
+    let assignee = match pick_assignee(client, issue).await? {
+        Some(assignee) => assignee,
+        None => return Ok(false), // User cancelled
+    };
+
+    let update = IssueUpdate {
+        assignee_id: Some(assignee.as_ref().map(|a| a.id.clone())),
+        ..Default::default()
+    };
+
+    if let Err(e) = client.update_issue(&issue.id, &update).await {
+        Command::new("notify-send")
+            .args(["Linear", &format!("Failed to update issue: {}", e)])
+            .spawn()
+            .ok();
+        return Err(e);
+    }
+
+    let message = match assignee {
+        Some(user) => format!("{} assigned to {}", issue.identifier, user.name),
+        None => format!("{} unassigned", issue.identifier),
+    };
+    Command::new("notify-send")
+        .args(["Linear", &message])
+        .spawn()
+        .ok();
+
+    Ok(true)
+}
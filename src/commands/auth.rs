@@ -1,6 +1,8 @@
 use anyhow::{Context, Result};
 use std::io::{self, Write};
 
+use crate::cache;
+use crate::commands::assign;
 use crate::config;
 use crate::linear::LinearClient;
 
@@ -39,6 +41,7 @@ pub async fn run() -> Result<()> {
 
     // Save the key
     config::set_api_key(api_key)?;
+    cache::set("viewer", "viewer", &viewer, assign::MEMBERS_TTL).ok();
 
     println!();
     println!("Success! Authenticated as {} ({})", viewer.name, viewer.email);
@@ -0,0 +1,350 @@
+use anyhow::Result;
+use std::collections::HashMap;
+use std::process::Command;
+
+use crate::commands::assign;
+use crate::frecency;
+use crate::launcher::Menu;
+use crate::linear::{self, IssueSummary, IssueUpdate, LinearClient};
+use crate::rofi;
+
+const ACTIONS: [&str; 6] = [
+    "Set state",
+    "Add label",
+    "Set priority",
+    "Assign",
+    "Move to cycle",
+    "Archive",
+];
+
+/// Per-issue outcome of a bulk action, in the order of the issues
+type Outcomes = Vec<Result<(), String>>;
+
+/// Pick an action and apply it to every issue in batched requests
+pub async fn run(client: &LinearClient, issues: &[IssueSummary]) -> Result<()> {
+    let options: Vec<String> = ACTIONS.iter().map(|a| a.to_string()).collect();
+    let prompt = format!("{} issues", issues.len());
+    let menu = Menu {
+        id: Some("bulk"),
+        prompt: &prompt,
+        options: &options,
+        ..Default::default()
+    };
+
+    let action = match rofi::menu(&menu)? {
+        Some(selection) => selection.index(),
+        None => return Ok(()), // User cancelled
+    };
+
+    let outcomes = match action {
+        0 => set_state(client, issues).await?,
+        1 => add_labels(client, issues).await?,
+        2 => set_priority(client, issues).await?,
+        3 => set_assignee(client, issues).await?,
+        4 => set_cycle(client, issues).await?,
+        _ => archive(client, issues).await?,
+    };
+
+    if let Some(outcomes) = outcomes {
+        summarize(ACTIONS[action], issues, &outcomes);
+    }
+
+    Ok(())
+}
+
+/// Move each issue to its team's first completed state
+pub async fn mark_done(client: &LinearClient, issues: &[IssueSummary]) -> Result<()> {
+    let mut done = HashMap::new();
+    for team_id in team_ids(issues) {
+        let state = client
+            .get_workflow_states(&team_id)
+            .await?
+            .into_iter()
+            .find(|s| s.state_type == "completed");
+        done.insert(team_id, state);
+    }
+
+    let outcomes = apply(client, issues, |issue| {
+        let state = done[&issue.team.id]
+            .as_ref()
+            .ok_or_else(|| format!("no completed state in {}", issue.team.key))?;
+        Ok(IssueUpdate {
+            state_id: Some(state.id.clone()),
+            ..Default::default()
+        })
+    })
+    .await?;
+
+    summarize("Mark done", issues, &outcomes);
+    Ok(())
+}
+
+/// Build an update per issue and send the ones that could be built
+async fn apply<F>(client: &LinearClient, issues: &[IssueSummary], build: F) -> Result<Outcomes>
+where
+    F: Fn(&IssueSummary) -> Result<IssueUpdate, String>,
+{
+    let mut outcomes: Outcomes = Vec::with_capacity(issues.len());
+    let mut updates = Vec::new();
+    let mut sent = Vec::new();
+
+    for (i, issue) in issues.iter().enumerate() {
+        match build(issue) {
+            Ok(update) => {
+                updates.push((issue.id.clone(), update));
+                sent.push(i);
+                outcomes.push(Ok(()));
+            }
+            Err(e) => outcomes.push(Err(e)),
+        }
+    }
+
+    let results = client.update_issues(&updates).await?;
+    for (i, result) in sent.into_iter().zip(results) {
+        outcomes[i] = result;
+    }
+
+    Ok(outcomes)
+}
+
+/// Distinct team ids of the issues, in order of first appearance
+fn team_ids(issues: &[IssueSummary]) -> Vec<String> {
+    let mut ids: Vec<String> = Vec::new();
+    for issue in issues {
+        if !ids.contains(&issue.team.id) {
+            ids.push(issue.team.id.clone());
+        }
+    }
+    ids
+}
+
+/// Distinct names, in order of first appearance
+fn unique_names<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut unique: Vec<String> = Vec::new();
+    for name in names {
+        if !unique.iter().any(|u| u == name) {
+            unique.push(name.to_string());
+        }
+    }
+    unique
+}
+
+/// States are per team, so they're matched across teams by name
+async fn set_state(client: &LinearClient, issues: &[IssueSummary]) -> Result<Option<Outcomes>> {
+    let teams = team_ids(issues);
+    let mut states = HashMap::new();
+    for team_id in &teams {
+        let team_states = client.get_workflow_states(team_id).await?;
+        states.insert(team_id.clone(), team_states);
+    }
+
+    // In team order, so the list reads the same on every run
+    let options = unique_names(
+        teams
+            .iter()
+            .flat_map(|id| &states[id])
+            .map(|s| s.name.as_str()),
+    );
+    let name = match rofi::select("State", &options)? {
+        Some(idx) => &options[idx],
+        None => return Ok(None),
+    };
+
+    let outcomes = apply(client, issues, |issue| {
+        let state = states[&issue.team.id]
+            .iter()
+            .find(|s| &s.name == name)
+            .ok_or_else(|| format!("no '{}' state in {}", name, issue.team.key))?;
+        Ok(IssueUpdate {
+            state_id: Some(state.id.clone()),
+            ..Default::default()
+        })
+    })
+    .await?;
+
+    Ok(Some(outcomes))
+}
+
+/// Labels are matched across teams by name and added to the existing ones
+async fn add_labels(client: &LinearClient, issues: &[IssueSummary]) -> Result<Option<Outcomes>> {
+    let teams = team_ids(issues);
+    let mut labels = HashMap::new();
+    for team_id in &teams {
+        let team_labels = client.get_labels(team_id).await?;
+        labels.insert(team_id.clone(), team_labels);
+    }
+
+    let mut options = unique_names(
+        teams
+            .iter()
+            .flat_map(|id| &labels[id])
+            .map(|l| l.name.as_str()),
+    );
+    frecency::sort("labels", &mut options, |name| name);
+    let names: Vec<&String> = match rofi::multi_select("Labels", &options)? {
+        Some(indices) => indices.iter().map(|&idx| &options[idx]).collect(),
+        None => return Ok(None),
+    };
+    for name in &names {
+        frecency::record("labels", name).ok();
+    }
+
+    let outcomes = apply(client, issues, |issue| {
+        let ids: Vec<String> = labels[&issue.team.id]
+            .iter()
+            .filter(|l| names.contains(&&l.name))
+            .map(|l| l.id.clone())
+            .collect();
+        if ids.is_empty() {
+            return Err(format!("labels not available in {}", issue.team.key));
+        }
+        Ok(IssueUpdate {
+            added_label_ids: Some(ids),
+            ..Default::default()
+        })
+    })
+    .await?;
+
+    Ok(Some(outcomes))
+}
+
+async fn set_priority(client: &LinearClient, issues: &[IssueSummary]) -> Result<Option<Outcomes>> {
+    let options: Vec<String> = linear::PRIORITIES
+        .iter()
+        .map(|(_, name)| name.to_string())
+        .collect();
+
+    let priority = match rofi::select("Priority", &options)? {
+        Some(idx) => linear::PRIORITIES[idx].0,
+        None => return Ok(None),
+    };
+
+    let outcomes = apply(client, issues, |_| {
+        Ok(IssueUpdate {
+            priority: Some(priority),
+            ..Default::default()
+        })
+    })
+    .await?;
+
+    Ok(Some(outcomes))
+}
+
+/// Offer the members of every team involved, viewer first and "Unassign" last
+async fn set_assignee(client: &LinearClient, issues: &[IssueSummary]) -> Result<Option<Outcomes>> {
+    let viewer = assign::viewer(client).await?;
+
+    let mut members = Vec::new();
+    for team_id in team_ids(issues) {
+        for member in assign::members(client, &team_id).await? {
+            if !members.iter().any(|m: &linear::User| m.id == member.id) {
+                members.push(member);
+            }
+        }
+    }
+    assign::sort_members(&mut members, &viewer);
+
+    let mut options: Vec<String> = members.iter().map(|m| m.name.clone()).collect();
+    options.push("Unassign".to_string());
+
+    let assignee_id = match assign::select_member("Assign", &options, &members)? {
+        Some(idx) if idx < members.len() => Some(members[idx].id.clone()),
+        Some(_) => None,
+        None => return Ok(None),
+    };
+
+    let outcomes = apply(client, issues, |_| {
+        Ok(IssueUpdate {
+            assignee_id: Some(assignee_id.clone()),
+            ..Default::default()
+        })
+    })
+    .await?;
+
+    Ok(Some(outcomes))
+}
+
+/// Cycles don't carry across teams, so this needs issues from one team
+async fn set_cycle(client: &LinearClient, issues: &[IssueSummary]) -> Result<Option<Outcomes>> {
+    let teams = team_ids(issues);
+    if teams.len() > 1 {
+        rofi::error("Moving to a cycle needs issues from a single team.")?;
+        return Ok(None);
+    }
+
+    let cycles = client.get_cycles(&teams[0]).await?;
+    if cycles.is_empty() {
+        rofi::error("No current or upcoming cycles.")?;
+        return Ok(None);
+    }
+
+    let options: Vec<String> = cycles
+        .iter()
+        .map(|c| match &c.name {
+            Some(name) => format!("Cycle {} - {}", c.number, name),
+            None => format!("Cycle {}", c.number),
+        })
+        .collect();
+
+    let cycle_id = match rofi::select("Cycle", &options)? {
+        Some(idx) => cycles[idx].id.clone(),
+        None => return Ok(None),
+    };
+
+    let outcomes = apply(client, issues, |_| {
+        Ok(IssueUpdate {
+            cycle_id: Some(cycle_id.clone()),
+            ..Default::default()
+        })
+    })
+    .await?;
+
+    Ok(Some(outcomes))
+}
+
+/// Archive the issues once the user confirms
+async fn archive(client: &LinearClient, issues: &[IssueSummary]) -> Result<Option<Outcomes>> {
+    let prompt = format!("Archive {} issues?", issues.len());
+    let options = vec!["Archive".to_string(), "Cancel".to_string()];
+    let menu = Menu {
+        id: Some("archive"),
+        prompt: &prompt,
+        options: &options,
+        ..Default::default()
+    };
+    if rofi::menu(&menu)?.map(|s| s.index()) != Some(0) {
+        return Ok(None);
+    }
+
+    let ids: Vec<String> = issues.iter().map(|i| i.id.clone()).collect();
+    Ok(Some(client.archive_issues(&ids).await?))
+}
+
+/// Notify with the number of successes and the reason for each failure
+fn summarize(action: &str, issues: &[IssueSummary], outcomes: &[Result<(), String>]) {
+    let failures: Vec<String> = issues
+        .iter()
+        .zip(outcomes)
+        .filter_map(|(issue, outcome)| {
+            outcome
+                .as_ref()
+                .err()
+                .map(|e| format!("{}: {}", issue.identifier, e))
+        })
+        .collect();
+    let succeeded = outcomes.len() - failures.len();
+
+    let mut body = format!("{}: {} succeeded", action, succeeded);
+    if !failures.is_empty() {
+        body.push_str(&format!(
+            ", {} failed\n{}",
+            failures.len(),
+            failures.join("\n")
+        ));
+    }
+
+    Command::new("notify-send")
+        .args(["Linear", &body])
+        .spawn()
+        .ok();
+}
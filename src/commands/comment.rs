@@ -0,0 +1,74 @@
+use anyhow::{Context, Result};
+use std::io::{self, Read};
+use std::process::Command;
+
+use crate::commands::issues;
+use crate::config;
+use crate::linear::{Comment, IssueSummary, LinearClient};
+use crate::rofi;
+
+pub async fn run(identifier: Option<String>, stdin: bool) -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    // Read piped input up front so a failed pick doesn't leave it unread
+    let piped = if stdin {
+        let mut body = String::new();
+        io::stdin()
+            .read_to_string(&mut body)
+            .context("Failed to read comment from stdin")?;
+        if body.trim().is_empty() {
+            anyhow::bail!("No comment provided on stdin");
+        }
+        Some(body)
+    } else {
+        None
+    };
+
+    let client = LinearClient::new(&api_key);
+    let issue = match issues::pick(&client, identifier.as_deref()).await? {
+        Some(issue) => issue,
+        None => return Ok(()), // User cancelled
+    };
+
+    match piped {
+        Some(body) => {
+            let comment = post(&client, &issue, &body).await?;
+            println!("{}", comment.url);
+            Ok(())
+        }
+        None => apply(&client, &issue).await,
+    }
+}
+
+/// Prompt for a comment on an issue and post it
+pub async fn apply(client: &LinearClient, issue: &IssueSummary) -> Result<()> {
+    let prompt = format!("Comment on {}", issue.identifier);
+    let body = match rofi::input_lines("comment", &prompt, "Comment...")? {
+        Some(body) => body,
+        None => return Ok(()), // User cancelled
+    };
+
+    post(client, issue, &body).await?;
+
+    Command::new("notify-send")
+        .args(["Linear", &format!("Commented on {}", issue.identifier)])
+        .spawn()
+        .ok();
+
+    Ok(())
+}
+
+async fn post(client: &LinearClient, issue: &IssueSummary, body: &str) -> Result<Comment> {
+    match client.create_comment(&issue.id, body).await {
+        Ok(comment) => Ok(comment),
+        Err(e) => {
+            Command::new("notify-send")
+                .args(["Linear", &format!("Failed to add comment: {}", e)])
+                .spawn()
+                .ok();
+            Err(e)
+        }
+    }
+}
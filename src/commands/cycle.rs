@@ -0,0 +1,93 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+
+use crate::commands::{issues, show};
+use crate::config;
+use crate::launcher::Menu;
+use crate::linear::{IssueSummary, LinearClient};
+use crate::markup;
+use crate::rofi;
+
+pub async fn run(team: Option<String>) -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let (_alias, team_config) = config::get_team(team.as_deref())?.context("Team not found")?;
+
+    let client = LinearClient::new(&api_key);
+    let cycle = match client.get_active_cycle(&team_config.id).await? {
+        Some(cycle) => cycle,
+        None => {
+            rofi::error(&format!("{} has no active cycle.", team_config.name))?;
+            return Ok(());
+        }
+    };
+
+    // Group issues under their workflow state, in the team's state order
+    let states = client.get_workflow_states(&team_config.id).await?;
+    let mut rows = issues::Rows::new()?;
+    let mut row_issues: Vec<Option<&IssueSummary>> = Vec::new();
+
+    for state in &states {
+        let in_state: Vec<&IssueSummary> = cycle
+            .issues
+            .nodes
+            .iter()
+            .filter(|i| i.state.id == state.id)
+            .collect();
+        if in_state.is_empty() {
+            continue;
+        }
+
+        rows.push(format!(
+            "<b>── {} ({}) ──</b>",
+            issues::state_markup(&state.name, &state.color),
+            in_state.len()
+        ));
+        row_issues.push(None);
+        for issue in in_state {
+            rows.push_issue(issue, "    ");
+            row_issues.push(Some(issue));
+        }
+    }
+
+    if rows.options.is_empty() {
+        rofi::error(&format!("Cycle {} has no issues.", cycle.number))?;
+        return Ok(());
+    }
+
+    let title = match &cycle.name {
+        Some(name) => format!("Cycle {} - {}", cycle.number, name),
+        None => format!("Cycle {}", cycle.number),
+    };
+    let days_left = (cycle.ends_at - Utc::now()).num_days().max(0);
+    let mesg = format!(
+        "<b>{}</b>\n{:.0}% complete, {} days remaining",
+        markup::escape(&title),
+        cycle.progress * 100.0,
+        days_left
+    );
+
+    let menu = Menu {
+        id: Some("cycle"),
+        prompt: &team_config.name,
+        options: &rows.options,
+        mesg: Some(&mesg),
+        markup: true,
+        icons: &rows.icons,
+        ..Default::default()
+    };
+
+    // Picking a header just shows the list again
+    loop {
+        match rofi::menu(&menu)? {
+            Some(selection) => {
+                if let Some(issue) = row_issues[selection.index()] {
+                    return show::show(&client, issue).await;
+                }
+            }
+            None => return Ok(()), // User cancelled
+        }
+    }
+}
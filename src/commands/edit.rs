@@ -0,0 +1,218 @@
+use anyhow::Result;
+use std::process::Command;
+
+use crate::commands;
+use crate::config::TeamConfig;
+use crate::frecency;
+use crate::launcher::Menu;
+use crate::linear::{self, Issue, IssueLabel, IssueUpdate, LinearClient, WorkflowState};
+use crate::rofi;
+
+/// Reopen a just-created issue in a rofi wizard and apply any changes
+pub async fn run(
+    client: &LinearClient,
+    issue: &Issue,
+    team: TeamConfig,
+    title: String,
+    description: Option<String>,
+) -> Result<()> {
+    let mut update = IssueUpdate::default();
+    let mut team = team;
+    let mut title = title;
+    let mut description = description;
+    let mut state: Option<WorkflowState> = None;
+    let mut priority: Option<u8> = None;
+    let mut labels: Vec<IssueLabel> = Vec::new();
+
+    // Fetched lazily and dropped when the team changes
+    let mut states: Option<Vec<WorkflowState>> = None;
+    let mut team_labels: Option<Vec<IssueLabel>> = None;
+
+    loop {
+        let label_names: Vec<&str> = labels.iter().map(|l| l.name.as_str()).collect();
+        let options = vec![
+            format!("Title: {}", title),
+            format!(
+                "Description: {}",
+                description.as_deref().unwrap_or("(none)")
+            ),
+            format!("Team: {}", team.name),
+            format!(
+                "State: {}",
+                state
+                    .as_ref()
+                    .map(|s| s.name.as_str())
+                    .unwrap_or("(unchanged)")
+            ),
+            format!(
+                "Priority: {}",
+                priority.map(linear::priority_name).unwrap_or("(unchanged)")
+            ),
+            format!(
+                "Labels: {}",
+                if label_names.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    label_names.join(", ")
+                }
+            ),
+            "Save".to_string(),
+        ];
+
+        let prompt = format!("Edit {}", issue.identifier);
+        let menu = Menu {
+            id: Some("edit"),
+            prompt: &prompt,
+            options: &options,
+            ..Default::default()
+        };
+        let Some(idx) = rofi::menu(&menu)?.map(|s| s.index()) else {
+            if update.is_empty() {
+                return Ok(()); // User cancelled
+            }
+            // Don't drop staged edits on a stray Escape; cancelling this
+            // returns to the fields
+            let options = vec!["Save".to_string(), "Discard".to_string()];
+            match rofi::select("Discard changes?", &options)? {
+                Some(0) => break,
+                Some(_) => return Ok(()),
+                None => continue,
+            }
+        };
+
+        match idx {
+            0 => {
+                if let Some(t) = rofi::input_prefilled("Title", "Issue title...", &title)? {
+                    title = t.clone();
+                    update.title = Some(t);
+                }
+            }
+            1 => {
+                let current = description.as_deref().unwrap_or("");
+                if let Some(d) =
+                    rofi::input_prefilled("Description", "Optional description...", current)?
+                {
+                    description = Some(d.clone());
+                    update.description = Some(d);
+                }
+            }
+            2 => {
+                if let Some((_, selected)) = commands::run::pick_team(Some(&team.id))? {
+                    if selected.id != team.id {
+                        update.team_id = Some(selected.id.clone());
+                        team = selected;
+
+                        // States and labels belong to the old team
+                        states = None;
+                        team_labels = None;
+                        state = None;
+                        update.state_id = None;
+                        if !labels.is_empty() {
+                            labels.clear();
+                            update.label_ids = Some(Vec::new());
+                        }
+                    }
+                }
+            }
+            3 => {
+                if states.is_none() {
+                    states = Some(client.get_workflow_states(&team.id).await?);
+                }
+                let available = states.as_ref().unwrap();
+                let options: Vec<String> = available.iter().map(|s| s.name.clone()).collect();
+
+                if let Some(idx) = rofi::select("State", &options)? {
+                    let selected = available[idx].clone();
+                    update.state_id = Some(selected.id.clone());
+                    state = Some(selected);
+                }
+            }
+            4 => {
+                let options: Vec<String> = linear::PRIORITIES
+                    .iter()
+                    .map(|(_, name)| name.to_string())
+                    .collect();
+
+                if let Some(idx) = rofi::select("Priority", &options)? {
+                    let (value, _) = linear::PRIORITIES[idx];
+                    priority = Some(value);
+                    update.priority = Some(value);
+                }
+            }
+            5 => {
+                if team_labels.is_none() {
+                    let mut fetched = client.get_labels(&team.id).await?;
+                    frecency::sort("labels", &mut fetched, |l| &l.name);
+                    team_labels = Some(fetched);
+                }
+                let available = team_labels.as_ref().unwrap();
+
+                // Toggle labels one at a time until "Done" is picked, keeping
+                // the last toggled row highlighted
+                let mut selected = None;
+                loop {
+                    let mut options: Vec<String> = available
+                        .iter()
+                        .map(|l| {
+                            let marker = if labels.iter().any(|s| s.id == l.id) {
+                                "[x]"
+                            } else {
+                                "[ ]"
+                            };
+                            format!("{} {}", marker, l.name)
+                        })
+                        .collect();
+                    options.push("Done".to_string());
+
+                    let menu = Menu {
+                        prompt: "Labels",
+                        options: &options,
+                        selected,
+                        ..Default::default()
+                    };
+                    match rofi::menu(&menu)?.map(|s| s.index()) {
+                        Some(idx) if idx < available.len() => {
+                            let label = &available[idx];
+                            if let Some(pos) = labels.iter().position(|s| s.id == label.id) {
+                                labels.remove(pos);
+                            } else {
+                                frecency::record("labels", &label.name).ok();
+                                labels.push(label.clone());
+                            }
+                            selected = Some(idx);
+                        }
+                        _ => break,
+                    }
+                }
+
+                update.label_ids = Some(labels.iter().map(|l| l.id.clone()).collect());
+            }
+            _ => break,
+        }
+    }
+
+    if update.is_empty() {
+        return Ok(());
+    }
+
+    let updated = match client.update_issue(&issue.id, &update).await {
+        Ok(issue) => issue,
+        Err(e) => {
+            Command::new("notify-send")
+                .args(["Linear", &format!("Failed to update issue: {}", e)])
+                .spawn()
+                .ok();
+            return Err(e);
+        }
+    };
+
+    Command::new("notify-send")
+        .args([
+            "Linear",
+            &format!("Updated {} - {}", updated.identifier, updated.title),
+        ])
+        .spawn()
+        .ok();
+
+    Ok(())
+}
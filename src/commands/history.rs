@@ -0,0 +1,169 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Days, Local, NaiveDate, Utc};
+use clap::ValueEnum;
+
+use crate::history::{self, Action, Entry};
+
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum Format {
+    Text,
+    Json,
+}
+
+/// Parse `YYYY-MM-DD`, `today`, `yesterday` or a number of days ago like `7d`,
+/// relative to `today`
+fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate> {
+    let days_ago = |n: u64| {
+        today
+            .checked_sub_days(Days::new(n))
+            .context("Date out of range")
+    };
+
+    match input {
+        "today" => Ok(today),
+        "yesterday" => days_ago(1),
+        _ => match input.strip_suffix('d').and_then(|n| n.parse().ok()) {
+            Some(n) => days_ago(n),
+            None => NaiveDate::parse_from_str(input, "%Y-%m-%d").with_context(|| {
+                format!(
+                    "Invalid date '{}' (use YYYY-MM-DD, today, yesterday or 7d)",
+                    input
+                )
+            }),
+        },
+    }
+}
+
+/// Start of a local calendar day
+fn start_of_day(date: NaiveDate) -> Result<DateTime<Utc>> {
+    date.and_hms_opt(0, 0, 0)
+        .and_then(|t| t.and_local_timezone(Local).earliest())
+        .map(|t| t.with_timezone(&Utc))
+        .context("Invalid local date")
+}
+
+pub async fn run(
+    team: Option<String>,
+    since: Option<String>,
+    until: Option<String>,
+    all: bool,
+    format: Format,
+) -> Result<()> {
+    let today = Local::now().date_naive();
+    let since = since
+        .as_deref()
+        .map(|d| parse_date(d, today))
+        .transpose()?
+        .map(start_of_day)
+        .transpose()?;
+
+    // `until` is inclusive, so stop at the start of the following day
+    let until = until
+        .as_deref()
+        .map(|d| parse_date(d, today))
+        .transpose()?
+        .map(|d| {
+            start_of_day(
+                d.checked_add_days(Days::new(1))
+                    .context("Date out of range")?,
+            )
+        })
+        .transpose()?;
+
+    let entries: Vec<Entry> = history::load()?
+        .into_iter()
+        .filter(|e| all || e.action == Action::Created)
+        .filter(|e| team.is_none() || e.team == team)
+        .filter(|e| since.map_or(true, |s| e.timestamp >= s))
+        .filter(|e| until.map_or(true, |u| e.timestamp < u))
+        .collect();
+
+    match format {
+        Format::Json => {
+            println!("{}", serde_json::to_string_pretty(&entries)?);
+        }
+        Format::Text => {
+            if entries.is_empty() {
+                println!("No matching issues in history.");
+                return Ok(());
+            }
+            for entry in &entries {
+                let action = match (all, entry.action) {
+                    (false, _) => "",
+                    (true, Action::Created) => "created  ",
+                    (true, Action::Viewed) => "viewed   ",
+                };
+                println!(
+                    "{}  {}{:<8}  {:<10}  {}  {}",
+                    entry
+                        .timestamp
+                        .with_timezone(&Local)
+                        .format("%Y-%m-%d %H:%M"),
+                    action,
+                    entry.team.as_deref().unwrap_or("-"),
+                    entry.identifier,
+                    entry.title,
+                    entry.url
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parses_relative_dates() {
+        let today = date(2026, 3, 1);
+        assert_eq!(parse_date("today", today).unwrap(), today);
+        assert_eq!(parse_date("yesterday", today).unwrap(), date(2026, 2, 28));
+        assert_eq!(parse_date("0d", today).unwrap(), today);
+        assert_eq!(parse_date("7d", today).unwrap(), date(2026, 2, 22));
+        assert_eq!(parse_date("365d", today).unwrap(), date(2025, 3, 1));
+    }
+
+    #[test]
+    fn parses_absolute_dates() {
+        let today = date(2026, 3, 1);
+        assert_eq!(parse_date("2024-02-29", today).unwrap(), date(2024, 2, 29));
+        assert_eq!(parse_date("2030-12-31", today).unwrap(), date(2030, 12, 31));
+    }
+
+    #[test]
+    fn rejects_invalid_dates() {
+        let today = date(2026, 3, 1);
+        for input in [
+            "",
+            "tomorrow",
+            "Today",
+            "d",
+            "-1d",
+            "7 d",
+            "7days",
+            "2026-02-30",
+            "2026-13-01",
+            "01-03-2026",
+            "2026/03/01",
+        ] {
+            assert!(parse_date(input, today).is_err(), "{:?}", input);
+        }
+
+        let error = parse_date("soon", today).unwrap_err().to_string();
+        assert!(error.contains("'soon'"), "{}", error);
+    }
+
+    #[test]
+    fn rejects_days_out_of_range() {
+        let today = date(2026, 3, 1);
+        assert!(parse_date("99999999999d", today).is_err());
+        assert!(parse_date("99999999999999999999999d", today).is_err());
+    }
+}
@@ -0,0 +1,113 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::config;
+use crate::keys::{self, InboxAction};
+use crate::launcher::Menu;
+use crate::linear::{IssueSummary, LinearClient, Notification};
+use crate::rofi;
+
+/// Short label for a Linear notification type
+fn kind(notification_type: &str) -> &str {
+    match notification_type {
+        "issueMention" | "issueCommentMention" | "issueDescriptionMention" => "Mention",
+        "issueAssignedToYou" | "issueUnassignedFromYou" => "Assigned",
+        "issueNewComment" | "issueCommentReaction" => "Comment",
+        "issueStatusChanged" => "Status",
+        "issueCreated" => "Created",
+        "issuePriorityUrgent" => "Urgent",
+        other => other,
+    }
+}
+
+fn format_row(notification: &Notification, issue: &IssueSummary) -> String {
+    let actor = notification
+        .actor
+        .as_ref()
+        .map(|a| format!(" - {}", a.name))
+        .unwrap_or_default();
+
+    format!(
+        "[{}]  {}  {}{}",
+        kind(&notification.notification_type),
+        issue.identifier,
+        issue.title,
+        actor
+    )
+}
+
+pub async fn run() -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let client = LinearClient::new(&api_key);
+    let unread = client.get_unread_notifications().await?;
+
+    // Only issue notifications have something to open
+    let listed: Vec<(&Notification, &IssueSummary)> = unread
+        .iter()
+        .filter_map(|n| n.issue.as_ref().map(|issue| (n, issue)))
+        .collect();
+
+    if listed.is_empty() {
+        rofi::error("Inbox zero - no unread notifications.")?;
+        return Ok(());
+    }
+
+    let options: Vec<String> = listed
+        .iter()
+        .map(|(notification, issue)| format_row(notification, issue))
+        .collect();
+    let prompt = format!("Inbox ({})", listed.len());
+
+    let bindings = config::load_config()?.keys.inbox;
+    keys::validate("inbox", &bindings)?;
+
+    let hints = keys::hints(&bindings);
+    let menu = Menu {
+        id: Some("inbox"),
+        prompt: &prompt,
+        options: &options,
+        keys: &hints,
+        ..Default::default()
+    };
+    let selection = match rofi::menu(&menu)? {
+        Some(s) => s,
+        None => return Ok(()), // User cancelled
+    };
+
+    let action = selection
+        .custom_key
+        .and_then(|key| keys::action(&bindings, key));
+
+    match action {
+        None => {
+            let (notification, issue) = listed[selection.index()];
+            open::that(&issue.url).context("Failed to open browser")?;
+            client
+                .mark_notifications_read(std::slice::from_ref(&notification.id))
+                .await?;
+        }
+        Some(InboxAction::MarkRead) => {
+            let (notification, _) = listed[selection.index()];
+            client
+                .mark_notifications_read(std::slice::from_ref(&notification.id))
+                .await?;
+        }
+        Some(InboxAction::MarkAllRead) => {
+            // Everything unread, not just the issue notifications listed here
+            let ids: Vec<String> = unread.iter().map(|n| n.id.clone()).collect();
+            client.mark_notifications_read(&ids).await?;
+            Command::new("notify-send")
+                .args([
+                    "Linear",
+                    &format!("Marked {} notifications as read", ids.len()),
+                ])
+                .spawn()
+                .ok();
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,258 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::clipboard;
+use crate::commands::{bulk, show};
+use crate::config;
+use crate::history;
+use crate::icons;
+use crate::keys::{self, IssueAction};
+use crate::launcher::Menu;
+use crate::linear::{self, IssueSummary, LinearClient};
+use crate::markup;
+use crate::rofi;
+
+/// Format an issue as a Pango markup row: priority glyph, identifier with
+/// the team key dimmed, title, and the state in its workflow color
+pub fn format_row(issue: &IssueSummary, glyph: bool) -> String {
+    let (key, number) = match issue.identifier.strip_prefix(issue.team.key.as_str()) {
+        Some(number) => (issue.team.key.as_str(), number),
+        None => ("", issue.identifier.as_str()),
+    };
+
+    let mut row = String::new();
+    if glyph {
+        let glyph = linear::priority_glyph(issue.priority);
+        if issue.priority == 1 {
+            row.push_str(&format!("<span foreground=\"#eb5757\">{}</span> ", glyph));
+        } else {
+            row.push_str(&format!("<span alpha=\"60%\">{}</span> ", glyph));
+        }
+    }
+    row.push_str(&format!(
+        "<span alpha=\"50%\">{}</span>{}  {}  {}",
+        markup::escape(key),
+        markup::escape(number),
+        markup::escape(&issue.title),
+        state_markup(&issue.state.name, &issue.state.color)
+    ));
+    row
+}
+
+/// A state name in its workflow color
+pub fn state_markup(name: &str, color: &str) -> String {
+    if color.is_empty() {
+        markup::escape(name)
+    } else {
+        format!(
+            "<span foreground=\"{}\">{}</span>",
+            markup::escape(color),
+            markup::escape(name)
+        )
+    }
+}
+
+/// Markup rows for a menu, with a priority icon per issue when `icons` is
+/// enabled in the config
+pub struct Rows {
+    pub options: Vec<String>,
+    pub icons: Vec<String>,
+    show_icons: bool,
+}
+
+impl Rows {
+    pub fn new() -> Result<Self> {
+        Ok(Rows {
+            options: Vec::new(),
+            icons: Vec::new(),
+            show_icons: config::load_config()?.icons,
+        })
+    }
+
+    pub fn from_issues(issues: &[IssueSummary]) -> Result<Self> {
+        let mut rows = Rows::new()?;
+        for issue in issues {
+            rows.push_issue(issue, "");
+        }
+        Ok(rows)
+    }
+
+    pub fn push_issue(&mut self, issue: &IssueSummary, indent: &str) {
+        let row = format!("{}{}", indent, format_row(issue, !self.show_icons));
+        self.options.push(row);
+        if self.show_icons {
+            self.icons
+                .push(icons::priority(issue.priority).unwrap_or_default());
+        }
+    }
+
+    /// A row that isn't an issue, already in markup
+    pub fn push(&mut self, row: String) {
+        self.options.push(row);
+        if self.show_icons {
+            self.icons.push(String::new());
+        }
+    }
+}
+
+/// Fetch the viewer's open issues, most urgent first, then most recently updated
+pub async fn assigned(client: &LinearClient) -> Result<Vec<IssueSummary>> {
+    let mut issues = client.get_assigned_issues().await?;
+    issues.sort_by(|a, b| {
+        linear::priority_rank(a.priority)
+            .cmp(&linear::priority_rank(b.priority))
+            .then_with(|| b.updated_at.cmp(&a.updated_at))
+    });
+    Ok(issues)
+}
+
+/// Recent issues offered by `pick`, below the assigned ones
+const PICK_RECENT: usize = 10;
+
+/// Resolve an issue from an identifier, or let the user pick one of their
+/// issues, a recently created or viewed one, or search the workspace for it
+pub async fn pick(client: &LinearClient, identifier: Option<&str>) -> Result<Option<IssueSummary>> {
+    if let Some(identifier) = identifier {
+        let id = linear::parse_issue_ref(identifier)
+            .with_context(|| format!("Not a Linear issue identifier or URL: {}", identifier))?;
+        return client.get_issue(&id).await.map(Some);
+    }
+
+    let issues = assigned(client).await?;
+    let recent: Vec<history::Entry> = history::recent(PICK_RECENT)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|e| !issues.iter().any(|i| i.identifier == e.identifier))
+        .collect();
+
+    let mut rows = Rows::from_issues(&issues)?;
+    for entry in &recent {
+        rows.push(format!(
+            "<span alpha=\"50%\">Recent</span>  {}  {}",
+            markup::escape(&entry.identifier),
+            markup::escape(&entry.title)
+        ));
+    }
+    rows.push("<i>Search all issues...</i>".to_string());
+
+    let menu = Menu {
+        prompt: "Issue",
+        options: &rows.options,
+        markup: true,
+        icons: &rows.icons,
+        ..Default::default()
+    };
+    match rofi::menu(&menu)?.map(|s| s.index()) {
+        Some(idx) if idx < issues.len() => Ok(Some(issues[idx].clone())),
+        Some(idx) => match recent.get(idx - issues.len()) {
+            Some(entry) => client.get_issue(&entry.identifier).await.map(Some),
+            None => search(client).await,
+        },
+        None => Ok(None), // User cancelled
+    }
+}
+
+/// Prompt for a query and pick one of the matching issues
+pub async fn search(client: &LinearClient) -> Result<Option<IssueSummary>> {
+    let query = match rofi::input("Search", "Search issues...")? {
+        Some(q) => q,
+        None => return Ok(None),
+    };
+
+    let results = client.search_issues(&query).await?;
+    if results.is_empty() {
+        rofi::error(&format!("No issues match '{}'", query))?;
+        return Ok(None);
+    }
+
+    let rows = Rows::from_issues(&results)?;
+    let menu = Menu {
+        prompt: "Issue",
+        options: &rows.options,
+        markup: true,
+        icons: &rows.icons,
+        ..Default::default()
+    };
+    Ok(rofi::menu(&menu)?.map(|s| results[s.index()].clone()))
+}
+
+pub async fn run() -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let client = LinearClient::new(&api_key);
+    let issues = assigned(&client).await?;
+
+    if issues.is_empty() {
+        rofi::error("No open issues assigned to you.")?;
+        return Ok(());
+    }
+
+    browse(&client, "Issues", &issues).await
+}
+
+/// Show a list of issues; Enter opens the detail view, custom keys run the
+/// actions bound in `keys.issues`
+///
+/// Marking several rows with Shift+Enter offers bulk actions instead.
+pub async fn browse(client: &LinearClient, prompt: &str, issues: &[IssueSummary]) -> Result<()> {
+    let rows = Rows::from_issues(issues)?;
+    let bindings = config::load_config()?.keys.issues;
+    keys::validate("issues", &bindings)?;
+
+    let menu = Menu {
+        id: Some("issues"),
+        prompt,
+        options: &rows.options,
+        markup: true,
+        icons: &rows.icons,
+        keys: &keys::hints(&bindings),
+        ..Default::default()
+    };
+    let selection = match rofi::multi_menu(&menu)? {
+        Some(s) => s,
+        None => return Ok(()), // User cancelled
+    };
+
+    let selected: Vec<IssueSummary> = selection
+        .indices
+        .iter()
+        .map(|&idx| issues[idx].clone())
+        .collect();
+
+    let action = selection
+        .custom_key
+        .and_then(|key| keys::action(&bindings, key));
+
+    match action {
+        None if selected.len() > 1 => bulk::run(client, &selected).await?,
+        None => show::show(client, &selected[0]).await?,
+        Some(IssueAction::Open) => {
+            for issue in &selected {
+                open::that(&issue.url).context("Failed to open browser")?;
+            }
+        }
+        Some(IssueAction::MarkDone) => bulk::mark_done(client, &selected).await?,
+        Some(action) => {
+            let texts: Vec<&str> = selected
+                .iter()
+                .map(|i| {
+                    if action == IssueAction::CopyLink {
+                        i.url.as_str()
+                    } else {
+                        i.identifier.as_str()
+                    }
+                })
+                .collect();
+            let text = texts.join("\n");
+            clipboard::copy(&text)?;
+            Command::new("notify-send")
+                .args(["Linear", &format!("Copied {}", texts.join(", "))])
+                .spawn()
+                .ok();
+        }
+    }
+
+    Ok(())
+}
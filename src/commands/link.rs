@@ -1,8 +1,8 @@
 use anyhow::{Context, Result};
-use std::io::{self, Write};
 
 use crate::config;
 use crate::linear::LinearClient;
+use crate::rofi;
 
 pub async fn run() -> Result<()> {
     // Check for API key
@@ -20,45 +20,23 @@ pub async fn run() -> Result<()> {
         anyhow::bail!("No teams found in your Linear workspace");
     }
 
-    // Display teams
-    println!();
-    println!("Available teams:");
-    for (i, team) in teams.iter().enumerate() {
-        println!("  {}. {} ({})", i + 1, team.name, team.key);
-    }
-    println!();
-
     // Prompt for selection
-    print!("Select team (1-{}): ", teams.len());
-    io::stdout().flush()?;
-
-    let mut selection = String::new();
-    io::stdin().read_line(&mut selection)?;
-    let selection: usize = selection
-        .trim()
-        .parse()
-        .context("Invalid selection")?;
-
-    if selection < 1 || selection > teams.len() {
-        anyhow::bail!("Invalid selection");
-    }
-
-    let team = &teams[selection - 1];
-
-    // Prompt for alias
-    println!();
-    print!("Alias for this team (default: {}): ", team.key.to_lowercase());
-    io::stdout().flush()?;
-
-    let mut alias = String::new();
-    io::stdin().read_line(&mut alias)?;
-    let alias = alias.trim();
-    let alias = if alias.is_empty() {
-        team.key.to_lowercase()
-    } else {
-        alias.to_string()
+    let options: Vec<String> = teams
+        .iter()
+        .map(|t| format!("{} ({})", t.name, t.key))
+        .collect();
+    let team = match rofi::select("Link team", &options)? {
+        Some(idx) => &teams[idx],
+        None => return Ok(()), // User cancelled
     };
 
+    // Prompt for alias, defaulting to the team key
+    let alias =
+        match rofi::input_prefilled("Alias", "Alias for this team", &team.key.to_lowercase())? {
+            Some(alias) => alias,
+            None => return Ok(()), // User cancelled
+        };
+
     // Save the team
     config::add_team(&alias, &team.id, &team.name)?;
 
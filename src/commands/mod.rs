@@ -1,5 +1,20 @@
+pub mod assign;
 pub mod auth;
+pub mod bulk;
+pub mod comment;
+pub mod cycle;
+pub mod edit;
+pub mod history;
+pub mod inbox;
+pub mod issues;
 pub mod link;
 pub mod list;
+pub mod modi;
+pub mod open;
 pub mod run;
+pub mod search;
+pub mod show;
+pub mod status;
+pub mod triage;
 pub mod unlink;
+pub mod view;
@@ -0,0 +1,528 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::process::Command;
+
+use crate::clipboard;
+use crate::commands::{issues, show};
+use crate::config;
+use crate::history::{self, Action};
+use crate::icons;
+use crate::linear::{self, IssueSummary, LinearClient};
+use crate::markup;
+use crate::script::{self, Retv, Row};
+
+/// Issues listed under "Recent issues"
+const RECENT_LIMIT: usize = 20;
+
+/// Where the user is in the mode, carried between invocations in `ROFI_DATA`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+#[serde(tag = "step", rename_all = "snake_case")]
+enum Step {
+    #[default]
+    Menu,
+    Team,
+    Title {
+        team: String,
+    },
+    Description {
+        team: String,
+        title: String,
+    },
+    Created {
+        identifier: String,
+        title: String,
+        url: String,
+    },
+    Issues,
+    Recent,
+    Issue {
+        id: String,
+        back: Box<Step>,
+    },
+    State {
+        id: String,
+        team_id: String,
+        back: Box<Step>,
+    },
+}
+
+/// Handle one step of rofi's script-mode protocol for `-modi "linear:rofi-linear modi --"`
+///
+/// rofi runs us once per step: with nothing when the mode opens, with the
+/// picked row (its `info` in `ROFI_INFO`) or the typed text otherwise. The
+/// step we are on travels back and forth in `ROFI_DATA`, and printing no rows
+/// closes rofi.
+pub async fn run(entry: Option<String>) -> Result<()> {
+    let step: Step = script::data()
+        .and_then(|d| serde_json::from_str(&d).ok())
+        .unwrap_or_default();
+
+    let api_key = match config::get_api_key()? {
+        Some(key) => key,
+        None => {
+            script::option("prompt", "Linear");
+            script::option("message", "No API key found. Run 'rofi-linear auth' first.");
+            return Ok(());
+        }
+    };
+    let client = LinearClient::new(&api_key);
+
+    let current = step.clone();
+    let next = match script::retv() {
+        Retv::Selected => match script::info() {
+            Some(info) => select(&client, step, &info).await,
+            None => Ok(Some(step)),
+        },
+        Retv::Custom => input(&client, step, entry.unwrap_or_default()).await,
+        Retv::Initial | Retv::Key(_) => Ok(Some(step)),
+    };
+
+    match next {
+        Ok(Some(step)) => {
+            if let Err(e) = render(&client, &step).await {
+                fail(&step, &e);
+            }
+        }
+        Ok(None) => {} // Done: rofi closes on empty output
+        Err(e) => fail(&current, &e),
+    }
+
+    Ok(())
+}
+
+/// Act on a picked row and return the step to show next
+async fn select(client: &LinearClient, step: Step, info: &str) -> Result<Option<Step>> {
+    let (action, arg) = info.split_once(':').unwrap_or((info, ""));
+
+    let next = match action {
+        "back" => back(step, config::list_teams()?.len()),
+        "menu" => Step::Menu,
+        "issues" => Step::Issues,
+        "recent" => Step::Recent,
+        "create" => {
+            let teams = config::list_teams()?;
+            match teams.len() {
+                0 => anyhow::bail!("No teams linked. Run 'rofi-linear link' first."),
+                1 => Step::Title {
+                    team: teams.into_iter().next().unwrap().0,
+                },
+                _ => Step::Team,
+            }
+        }
+        "team" => Step::Title {
+            team: arg.to_string(),
+        },
+        "title" => match step {
+            Step::Title { team } => Step::Description {
+                team,
+                title: arg.to_string(),
+            },
+            step => step,
+        },
+        "skip" => match step {
+            Step::Description { team, title } => create(client, &team, &title, None).await?,
+            step => step,
+        },
+        "issue" => Step::Issue {
+            id: arg.to_string(),
+            back: Box::new(step),
+        },
+        "state" => match step {
+            Step::Issue { ref id, .. } => {
+                let issue = client.get_issue(id).await?;
+                Step::State {
+                    id: issue.id,
+                    team_id: issue.team.id,
+                    back: Box::new(step),
+                }
+            }
+            step => step,
+        },
+        "set-state" => match step {
+            Step::State { id, back, .. } => {
+                let update = linear::IssueUpdate {
+                    state_id: Some(arg.to_string()),
+                    ..Default::default()
+                };
+                let issue = client.update_issue(&id, &update).await?;
+                notify(&format!("Updated {} - {}", issue.identifier, issue.title));
+                *back
+            }
+            step => step,
+        },
+        "open" => {
+            open::that(arg).context("Failed to open browser")?;
+            return Ok(None);
+        }
+        "copy" => {
+            clipboard::copy(arg)?;
+            notify(&format!("Copied {}", arg));
+            return Ok(None);
+        }
+        _ => step,
+    };
+
+    Ok(Some(next))
+}
+
+/// Take typed text on the steps that ask for it; elsewhere it is ignored
+async fn input(client: &LinearClient, step: Step, text: String) -> Result<Option<Step>> {
+    let text = text.trim().to_string();
+    if text.is_empty() {
+        return Ok(Some(step));
+    }
+
+    let next = match step {
+        Step::Title { team } => Step::Description { team, title: text },
+        Step::Description { team, title } => create(client, &team, &title, Some(&text)).await?,
+        step => step,
+    };
+
+    Ok(Some(next))
+}
+
+/// The step before `step`, given how many teams are linked
+fn back(step: Step, teams: usize) -> Step {
+    match step {
+        Step::Title { .. } if teams > 1 => Step::Team,
+        Step::Description { team, .. } => Step::Title { team },
+        Step::Issue { back, .. } | Step::State { back, .. } => *back,
+        _ => Step::Menu,
+    }
+}
+
+async fn create(
+    client: &LinearClient,
+    team: &str,
+    title: &str,
+    description: Option<&str>,
+) -> Result<Step> {
+    let (alias, team) = config::get_team(Some(team))?.context("Team not found")?;
+    let issue = client.create_issue(&team.id, title, description).await?;
+
+    history::record(
+        Action::Created,
+        Some(&alias),
+        &issue.identifier,
+        &issue.url,
+        &issue.title,
+    )
+    .ok();
+
+    Ok(Step::Created {
+        identifier: issue.identifier,
+        title: issue.title,
+        url: issue.url,
+    })
+}
+
+/// Print the mode options and rows for a step
+async fn render(client: &LinearClient, step: &Step) -> Result<()> {
+    script::option("data", &serde_json::to_string(step)?);
+    reset();
+
+    match step {
+        Step::Menu => {
+            script::option("prompt", "Linear");
+            script::option("no-custom", "true");
+            Row::new("Create issue")
+                .info("create")
+                .icon("document-new")
+                .print();
+            Row::new("My issues")
+                .info("issues")
+                .icon("view-list")
+                .print();
+            Row::new("Recent issues")
+                .info("recent")
+                .icon("document-open-recent")
+                .print();
+        }
+        Step::Team => {
+            script::option("prompt", "Team");
+            script::option("no-custom", "true");
+            for (alias, team) in config::list_teams()? {
+                let text = format!("{} ({})", alias, team.name);
+                let info = format!("team:{}", alias);
+                Row::new(&text).info(&info).icon("system-users").print();
+            }
+            back_row();
+        }
+        Step::Title { team } => {
+            let title_history = config::load_config()?.title_history;
+            let scope = Some(team.as_str()).filter(|_| title_history.per_team);
+            let titles = history::titles(title_history.size, scope).unwrap_or_default();
+
+            let mut message = format!(
+                "New <b>{}</b> issue: type a title and press Enter",
+                markup::escape(team)
+            );
+            if !titles.is_empty() {
+                message.push_str(", or pick an earlier one (Ctrl+Enter uses the typed text)");
+            }
+            script::option("prompt", "Title");
+            script::option("message", &message);
+            for title in &titles {
+                Row::new(title)
+                    .info(&format!("title:{}", title))
+                    .icon("document-edit")
+                    .print();
+            }
+            back_row();
+        }
+        Step::Description { title, .. } => {
+            script::option("prompt", "Description");
+            script::option(
+                "message",
+                &format!(
+                    "<b>{}</b>\nType a description, or create the issue without one",
+                    markup::escape(title)
+                ),
+            );
+            Row::new("Create without description")
+                .info("skip")
+                .icon("document-save")
+                .print();
+            back_row();
+        }
+        Step::Created {
+            identifier,
+            title,
+            url,
+        } => {
+            script::option("prompt", identifier);
+            script::option("no-custom", "true");
+            script::option(
+                "message",
+                &format!(
+                    "Created <b>{}</b> - {}",
+                    markup::escape(identifier),
+                    markup::escape(title)
+                ),
+            );
+            let open = format!("open:{}", url);
+            let copy = format!("copy:{}", url);
+            Row::new("Open in browser")
+                .info(&open)
+                .icon("web-browser")
+                .print();
+            Row::new("Copy link").info(&copy).icon("edit-copy").print();
+            Row::new("Create another")
+                .info("create")
+                .icon("document-new")
+                .print();
+            Row::new("Back to menu")
+                .info("menu")
+                .icon("go-home")
+                .print();
+        }
+        Step::Issues => {
+            script::option("prompt", "Issues");
+            script::option("no-custom", "true");
+            let issues = issues::assigned(client).await?;
+            let show_icons = config::load_config()?.icons;
+            script::option("markup-rows", "true");
+            script::option(
+                "message",
+                &format!("{} open issues assigned to you", issues.len()),
+            );
+
+            // Group under a header row per state, in the order states first appear
+            let mut states: Vec<&str> = Vec::new();
+            for issue in &issues {
+                if !states.contains(&issue.state.name.as_str()) {
+                    states.push(&issue.state.name);
+                }
+            }
+            for state in states {
+                let in_state: Vec<&IssueSummary> =
+                    issues.iter().filter(|i| i.state.name == state).collect();
+                let header = format!(
+                    "<b>{}</b>",
+                    issues::state_markup(state, &in_state[0].state.color)
+                );
+                Row::new(&header).nonselectable().print();
+                for issue in in_state {
+                    let text = format!("  {}", issues::format_row(issue, !show_icons));
+                    let info = format!("issue:{}", issue.id);
+                    let icon = show_icons
+                        .then(|| icons::priority(issue.priority))
+                        .flatten();
+                    let mut row = Row::new(&text).info(&info);
+                    row.icon = icon.as_deref();
+                    row.print();
+                }
+            }
+            back_row();
+        }
+        Step::Recent => {
+            script::option("prompt", "Recent");
+            script::option("no-custom", "true");
+            for entry in history::recent(RECENT_LIMIT)? {
+                let text = format!("{}  {}", entry.identifier, entry.title);
+                let info = format!("issue:{}", entry.identifier);
+                let icon = match entry.action {
+                    Action::Created => "document-new",
+                    Action::Viewed => "document-open",
+                };
+                Row::new(&text).info(&info).icon(icon).print();
+            }
+            back_row();
+        }
+        Step::Issue { id, .. } => {
+            let details = client.get_issue_details(id).await?;
+            let issue = &details.summary;
+            history::record(
+                Action::Viewed,
+                config::alias_for_team_id(&issue.team.id)?.as_deref(),
+                &issue.identifier,
+                &issue.url,
+                &issue.title,
+            )
+            .ok();
+
+            script::option("prompt", &issue.identifier);
+            script::option("no-custom", "true");
+            script::option("message", &show::render(&details));
+            let open = format!("open:{}", issue.url);
+            let copy = format!("copy:{}", issue.url);
+            Row::new("Open").info(&open).icon("web-browser").print();
+            Row::new("Copy link").info(&copy).icon("edit-copy").print();
+            Row::new("Change state")
+                .info("state")
+                .icon("view-refresh")
+                .print();
+            back_row();
+        }
+        Step::State { team_id, .. } => {
+            script::option("prompt", "State");
+            script::option("no-custom", "true");
+            for state in client.get_workflow_states(team_id).await? {
+                let info = format!("set-state:{}", state.id);
+                Row::new(&state.name).info(&info).print();
+            }
+            back_row();
+        }
+    }
+
+    Ok(())
+}
+
+/// rofi keeps mode options between steps, so each step starts from these
+fn reset() {
+    script::option("no-custom", "false");
+    script::option("markup-rows", "false");
+}
+
+fn back_row() {
+    Row::new("Back").info("back").icon("go-previous").print();
+}
+
+/// Show an error in place of the step
+///
+/// The step is kept, so text typed again is handled as if the step had
+/// rendered normally.
+fn fail(step: &Step, error: &anyhow::Error) {
+    if let Ok(data) = serde_json::to_string(step) {
+        script::option("data", &data);
+    }
+    reset();
+    script::option("prompt", "Linear");
+    script::option(
+        "message",
+        &format!("<b>Error:</b> {}", markup::escape(&error.to_string())),
+    );
+    Row::new("Back to menu")
+        .info("menu")
+        .icon("go-home")
+        .print();
+}
+
+fn notify(body: &str) {
+    Command::new("notify-send")
+        .args(["Linear", body])
+        .spawn()
+        .ok();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue_from(back: Step) -> Step {
+        Step::Issue {
+            id: "ENG-1".to_string(),
+            back: Box::new(back),
+        }
+    }
+
+    #[test]
+    fn steps_round_trip_through_rofi_data() {
+        let steps = [
+            Step::Menu,
+            Step::Team,
+            Step::Description {
+                team: "eng".to_string(),
+                title: "Fix \"login\"\nnow".to_string(),
+            },
+            Step::Created {
+                identifier: "ENG-2".to_string(),
+                title: "Fix login".to_string(),
+                url: "https://linear.app/acme/issue/ENG-2".to_string(),
+            },
+            Step::State {
+                id: "abc".to_string(),
+                team_id: "team".to_string(),
+                back: Box::new(issue_from(Step::Recent)),
+            },
+        ];
+        for step in steps {
+            let data = serde_json::to_string(&step).unwrap();
+            assert!(!data.contains('\n'), "{}", data);
+            assert_eq!(serde_json::from_str::<Step>(&data).unwrap(), step);
+        }
+    }
+
+    #[test]
+    fn steps_are_tagged_by_name() {
+        let data = serde_json::to_value(issue_from(Step::Issues)).unwrap();
+        assert_eq!(
+            data,
+            serde_json::json!({
+                "step": "issue",
+                "id": "ENG-1",
+                "back": { "step": "issues" }
+            })
+        );
+    }
+
+    #[test]
+    fn back_from_create_steps() {
+        let title = || Step::Title {
+            team: "eng".to_string(),
+        };
+        let description = Step::Description {
+            team: "eng".to_string(),
+            title: "Fix login".to_string(),
+        };
+        assert_eq!(back(description, 2), title());
+        assert_eq!(back(title(), 2), Step::Team);
+        assert_eq!(back(title(), 1), Step::Menu);
+        assert_eq!(back(Step::Team, 2), Step::Menu);
+    }
+
+    #[test]
+    fn back_returns_to_where_the_issue_was_opened() {
+        assert_eq!(back(issue_from(Step::Recent), 1), Step::Recent);
+
+        let state = Step::State {
+            id: "abc".to_string(),
+            team_id: "team".to_string(),
+            back: Box::new(issue_from(Step::Issues)),
+        };
+        let issue = back(state, 1);
+        assert_eq!(issue, issue_from(Step::Issues));
+        assert_eq!(back(issue, 1), Step::Issues);
+        assert_eq!(back(Step::Menu, 1), Step::Menu);
+    }
+}
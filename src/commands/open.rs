@@ -0,0 +1,69 @@
+use anyhow::{Context, Result};
+
+use crate::config;
+use crate::history::{self, Action};
+use crate::linear::{self, LinearClient};
+use crate::rofi;
+
+/// Entries shown in the recent issues list
+const RECENT_LIMIT: usize = 50;
+
+pub async fn run(issue: Option<String>) -> Result<()> {
+    let Some(issue) = issue else {
+        return open_recent();
+    };
+
+    let identifier = linear::parse_issue_ref(&issue)
+        .with_context(|| format!("Not a Linear issue identifier or URL: {}", issue))?;
+
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let client = LinearClient::new(&api_key);
+    let issue = client.get_issue(&identifier).await?;
+
+    open::that(&issue.url).context("Failed to open browser")?;
+    let team = config::alias_for_team_id(&issue.team.id)?;
+    history::record(
+        Action::Viewed,
+        team.as_deref(),
+        &issue.identifier,
+        &issue.url,
+        &issue.title,
+    )
+    .ok();
+
+    Ok(())
+}
+
+/// Pick from recently created or viewed issues
+fn open_recent() -> Result<()> {
+    let recent = history::recent(RECENT_LIMIT)?;
+    if recent.is_empty() {
+        rofi::error("No recent issues yet.")?;
+        return Ok(());
+    }
+
+    let options: Vec<String> = recent
+        .iter()
+        .map(|e| format!("{}  {}", e.identifier, e.title))
+        .collect();
+
+    let entry = match rofi::select("Recent", &options)? {
+        Some(idx) => &recent[idx],
+        None => return Ok(()), // User cancelled
+    };
+
+    open::that(&entry.url).context("Failed to open browser")?;
+    history::record(
+        Action::Viewed,
+        entry.team.as_deref(),
+        &entry.identifier,
+        &entry.url,
+        &entry.title,
+    )
+    .ok();
+
+    Ok(())
+}
@@ -1,87 +1,227 @@
 use anyhow::{Context, Result};
 use std::process::Command;
 
+use crate::commands;
 use crate::config;
-use crate::linear::LinearClient;
+use crate::config::AppendMode;
+use crate::config::TeamConfig;
+use crate::frecency;
+use crate::history::{self, Action};
+use crate::keys::{self, Bindings, CreateAction};
+use crate::launcher::{self, Menu};
+use crate::linear::{self, IssueDetails, IssueUpdate, LinearClient};
+use crate::markup;
 use crate::rofi;
 
-fn notify(summary: &str, body: &str) -> bool {
-    let output = Command::new("notify-send")
-        .args([summary, body, "-A", "default=Open"])
-        .output()
-        .ok();
+/// Show a notification with actions and wait for the one the user picked
+fn notify(summary: &str, body: &str, actions: &[&str]) -> Option<String> {
+    let mut args = vec![summary.to_string(), body.to_string()];
+    for action in actions {
+        args.push("-A".to_string());
+        args.push(action.to_string());
+    }
+
+    let output = Command::new("notify-send").args(&args).output().ok()?;
+    let action = String::from_utf8_lossy(&output.stdout).trim().to_string();
 
-    match output {
-        Some(o) => String::from_utf8_lossy(&o.stdout).trim() == "default",
-        None => false,
+    if action.is_empty() {
+        None
+    } else {
+        Some(action)
     }
 }
 
+/// Steps of the create wizard
+#[derive(Clone, Copy, PartialEq)]
+enum Step {
+    Team,
+    Title,
+    Description,
+    Summary,
+}
+
+/// What the wizard has collected so far
+struct Draft {
+    team: Option<(String, TeamConfig)>,
+    title: String,
+    description: Option<String>,
+}
+
+/// How a wizard step ended
+enum Outcome {
+    Next,
+    Back,
+    /// Create the issue now, skipping any remaining steps
+    Submit,
+    Cancel,
+}
+
+const SUMMARY_ACTIONS: [&str; 3] = ["Submit", "Edit field", "Cancel"];
+
+/// Description lines previewed on the summary screen
+const DESCRIPTION_LINES: usize = 6;
+
 pub async fn run(team: Option<String>, quick: bool, open_issue: bool, multi_team: bool) -> Result<()> {
     // Check for API key
     let api_key = config::get_api_key()?.context(
         "No API key found. Run 'rofi-linear auth' first.",
     )?;
 
-    // Get the team to use
-    let (_team_alias, team_config) = if multi_team {
-        // Multi-team mode - always prompt for team selection
-        let teams = config::list_teams()?;
-        if teams.is_empty() {
-            rofi::error("No teams linked. Run 'rofi-linear link' first.")?;
-            return Ok(());
-        }
+    let teams = config::list_teams()?;
+    if teams.is_empty() {
+        rofi::error("No teams linked. Run 'rofi-linear link' first.")?;
+        return Ok(());
+    }
+
+    // Multi-team mode, or several teams and none given, asks for the team first
+    let team = match team {
+        Some(ref t) if !multi_team => Some(config::get_team(Some(t))?.context("Team not found")?),
+        _ if !multi_team && teams.len() == 1 => teams.into_iter().next(),
+        _ => None,
+    };
+    let ask_team = team.is_none();
 
-        let options: Vec<String> = teams
-            .iter()
-            .map(|(alias, t)| format!("{} ({})", alias, t.name))
-            .collect();
+    let config = config::load_config()?;
+    let title_history = config.title_history;
+    let bindings = config.keys.create;
+    keys::validate("create", &bindings)?;
+    let hints = keys::hints(&bindings);
 
-        match rofi::select("Team", &options)? {
-            Some(idx) => Some(teams.into_iter().nth(idx).unwrap()),
-            None => return Ok(()), // User cancelled
-        }
-    } else {
-        match team {
-            Some(ref t) => config::get_team(Some(t))?,
-            None => {
-                let teams = config::list_teams()?;
-                match teams.len() {
-                    0 => {
-                        rofi::error("No teams linked. Run 'rofi-linear link' first.")?;
-                        return Ok(());
+    let mut draft = Draft {
+        team,
+        title: String::new(),
+        description: None,
+    };
+    let mut step = if ask_team { Step::Team } else { Step::Title };
+    // Set while a field picked on the summary screen is being edited
+    let mut editing = false;
+
+    loop {
+        let outcome = match step {
+            Step::Team => {
+                let current = draft.team.as_ref().map(|(_, t)| t.id.as_str());
+                match pick_team(current)? {
+                    Some(team) => {
+                        draft.team = Some(team);
+                        Outcome::Next
                     }
-                    1 => Some(teams.into_iter().next().unwrap()),
-                    _ => {
-                        // Multiple teams - prompt for selection
-                        let options: Vec<String> = teams
-                            .iter()
-                            .map(|(alias, t)| format!("{} ({})", alias, t.name))
-                            .collect();
-
-                        match rofi::select("Team", &options)? {
-                            Some(idx) => Some(teams.into_iter().nth(idx).unwrap()),
-                            None => return Ok(()), // User cancelled
+                    None => Outcome::Cancel,
+                }
+            }
+            Step::Title => {
+                let initial = Some(draft.title.as_str()).filter(|t| !t.is_empty());
+                let team = draft
+                    .team
+                    .as_ref()
+                    .filter(|_| title_history.per_team)
+                    .map(|(alias, _)| alias.as_str());
+                let suggestions = history::titles(title_history.size, team).unwrap_or_default();
+                match rofi::input_with_keys(
+                    "Title",
+                    "Issue title...",
+                    initial,
+                    &suggestions,
+                    &hints,
+                )? {
+                    None => Outcome::Cancel,
+                    Some((title, key)) => match key.and_then(|k| keys::action(&bindings, k)) {
+                        Some(CreateAction::Back) => Outcome::Back,
+                        // "Create now" needs a title, so ask for it again
+                        Some(_) if title.is_empty() => continue,
+                        _ if title.is_empty() => Outcome::Cancel,
+                        action => {
+                            // An identifier or URL of an existing issue as the
+                            // title targets that issue instead; titles that
+                            // merely look like one, with no such issue, fall
+                            // through
+                            if let Some(identifier) = linear::parse_issue_ref(&title) {
+                                let client = LinearClient::new(&api_key);
+                                match client.find_issue_details(&identifier).await {
+                                    Ok(Some(issue)) => return append(&client, issue).await,
+                                    Ok(None) => {}
+                                    Err(e) => {
+                                        Command::new("notify-send")
+                                            .args([
+                                                "Linear",
+                                                &format!("Failed to look up {}: {}", identifier, e),
+                                            ])
+                                            .spawn()
+                                            .ok();
+                                        return Err(e);
+                                    }
+                                }
+                            }
+
+                            draft.title = title;
+                            if action == Some(CreateAction::Submit) {
+                                Outcome::Submit
+                            } else {
+                                Outcome::Next
+                            }
                         }
-                    }
+                    },
                 }
             }
-        }
-    }
-    .context("Team not found")?;
+            Step::Description => {
+                let initial = draft.description.as_deref();
+                match rofi::input_with_keys(
+                    "Description",
+                    "Optional description...",
+                    initial,
+                    &[],
+                    &hints,
+                )? {
+                    None => Outcome::Cancel,
+                    Some((text, key)) => match key.and_then(|k| keys::action(&bindings, k)) {
+                        Some(CreateAction::Back) => Outcome::Back,
+                        action => {
+                            draft.description = Some(text).filter(|t| !t.trim().is_empty());
+                            if action == Some(CreateAction::Submit) {
+                                Outcome::Submit
+                            } else {
+                                Outcome::Next
+                            }
+                        }
+                    },
+                }
+            }
+            Step::Summary => summary(&draft, &bindings)?,
+        };
 
-    // Prompt for issue title
-    let title = match rofi::input("Title", "Issue title...")? {
-        Some(t) if !t.is_empty() => t,
-        _ => return Ok(()), // User cancelled or empty
-    };
+        step = match outcome {
+            // Leaving an edited field any way but submitting, Escape
+            // included, returns to the summary with the draft kept
+            Outcome::Next | Outcome::Back | Outcome::Cancel if editing => {
+                editing = false;
+                Step::Summary
+            }
+            Outcome::Cancel => return Ok(()),
+            Outcome::Submit => break,
+            Outcome::Next => match step {
+                Step::Team => Step::Title,
+                Step::Title if quick => break,
+                Step::Title => Step::Description,
+                Step::Description => Step::Summary,
+                Step::Summary => match edit_field(&draft)? {
+                    Some(field) => {
+                        editing = true;
+                        field
+                    }
+                    None => Step::Summary,
+                },
+            },
+            Outcome::Back => match step {
+                Step::Summary => Step::Description,
+                Step::Description => Step::Title,
+                Step::Title if ask_team => Step::Team,
+                other => other,
+            },
+        };
+    }
 
-    // Prompt for description (unless quick mode)
-    let description = if quick {
-        None
-    } else {
-        rofi::input_multiline("Description", "Optional description...")?
-    };
+    let (team_alias, team_config) = draft.team.context("Team not found")?;
+    let title = draft.title;
+    let description = draft.description;
 
     // Create the issue
     let client = LinearClient::new(&api_key);
@@ -99,16 +239,206 @@ pub async fn run(team: Option<String>, quick: bool, open_issue: bool, multi_team
         }
     };
 
+    history::record(
+        Action::Created,
+        Some(&team_alias),
+        &issue.identifier,
+        &issue.url,
+        &issue.title,
+    )
+    .ok();
+
     // Open in browser if requested, or if notification clicked
-    if open_issue {
+    let summary = format!("{} - {}", issue.identifier, issue.title);
+    let action = if !launcher::has_display() {
+        // Notifications don't reach a terminal session, e.g. over SSH, with
+        // either terminal launcher
+        println!("Created {}\n{}", summary, issue.url);
+        if open_issue {
+            open::that(&issue.url).ok();
+        }
+        None
+    } else if open_issue {
         open::that(&issue.url).ok();
+        notify("Linear", &summary, &["edit=Edit"])
+    } else {
+        notify("Linear", &summary, &["default=Open", "edit=Edit"])
+    };
+
+    match action.as_deref() {
+        Some("default") => {
+            open::that(&issue.url).ok();
+        }
+        Some("edit") => {
+            commands::edit::run(&client, &issue, team_config, title, description).await?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+/// Append text to an existing issue as a comment or to its description
+async fn append(client: &LinearClient, issue: IssueDetails) -> Result<()> {
+    let prompt = format!("Append to {}", issue.summary.identifier);
+    let text = match rofi::input_lines("append", &prompt, &issue.summary.title)? {
+        Some(text) => text,
+        None => return Ok(()), // User cancelled
+    };
+
+    let mode = config::load_config()?.append_mode;
+    let result = match mode {
+        AppendMode::Comment => client
+            .create_comment(&issue.summary.id, &text)
+            .await
+            .map(|_| ()),
+        AppendMode::Description => {
+            let description = match issue.description.as_deref() {
+                Some(existing) if !existing.trim().is_empty() => {
+                    format!("{}\n\n{}", existing.trim_end(), text)
+                }
+                _ => text,
+            };
+            let update = IssueUpdate {
+                description: Some(description),
+                ..Default::default()
+            };
+            client
+                .update_issue(&issue.summary.id, &update)
+                .await
+                .map(|_| ())
+        }
+    };
+
+    if let Err(e) = result {
         Command::new("notify-send")
-            .args(["Linear", &format!("{} - {}", issue.identifier, issue.title)])
+            .args([
+                "Linear",
+                &format!("Failed to update {}: {}", issue.summary.identifier, e),
+            ])
             .spawn()
             .ok();
-    } else if notify("Linear", &format!("{} - {}", issue.identifier, issue.title)) {
-        open::that(&issue.url).ok();
+        return Err(e);
+    }
+
+    let verb = match mode {
+        AppendMode::Comment => "Commented on",
+        AppendMode::Description => "Appended to",
+    };
+    if notify(
+        "Linear",
+        &format!(
+            "{} {} - {}",
+            verb, issue.summary.identifier, issue.summary.title
+        ),
+        &["default=Open"],
+    )
+    .as_deref()
+        == Some("default")
+    {
+        open::that(&issue.summary.url).ok();
     }
 
     Ok(())
 }
+
+/// Pick a linked team, most frecent first
+///
+/// The team with id `current` is highlighted instead when given, e.g. when
+/// changing an issue's team.
+pub fn pick_team(current: Option<&str>) -> Result<Option<(String, TeamConfig)>> {
+    let mut teams = config::list_teams()?;
+    frecency::sort("teams", &mut teams, |(alias, _)| alias);
+
+    let options: Vec<String> = teams
+        .iter()
+        .map(|(alias, t)| format!("{} ({})", alias, t.name))
+        .collect();
+    let selected = current
+        .and_then(|id| teams.iter().position(|(_, t)| t.id == id))
+        .or_else(|| frecency::best("teams", &teams, |(alias, _)| alias));
+
+    let menu = Menu {
+        prompt: "Team",
+        options: &options,
+        selected,
+        ..Default::default()
+    };
+    let Some(selection) = rofi::menu(&menu)? else {
+        return Ok(None);
+    };
+
+    let team = teams.swap_remove(selection.index());
+    frecency::record("teams", &team.0).ok();
+    Ok(Some(team))
+}
+
+/// Show what will be created, offering Submit, Edit field and Cancel
+fn summary(draft: &Draft, bindings: &Bindings<CreateAction>) -> Result<Outcome> {
+    let (alias, team) = draft.team.as_ref().context("Team not found")?;
+
+    let mut mesg = vec![
+        format!(
+            "<b>Team:</b> {} ({})",
+            markup::escape(alias),
+            markup::escape(&team.name)
+        ),
+        format!("<b>Title:</b> {}", markup::escape(&draft.title)),
+    ];
+    match &draft.description {
+        Some(description) => {
+            mesg.push("<b>Description:</b>".to_string());
+            mesg.push(markup::from_markdown(description, DESCRIPTION_LINES));
+        }
+        None => mesg.push("<b>Description:</b> <i>(none)</i>".to_string()),
+    }
+    let mesg = mesg.join("\n");
+
+    // Only bind the back key where it's native; emulated keys would add a
+    // follow-up menu to every pick
+    let back: Vec<(&str, &str)> = keys::hints(bindings)
+        .into_iter()
+        .zip(bindings.values())
+        .filter(|(_, action)| **action == CreateAction::Back && rofi::supports_keys())
+        .map(|(hint, _)| hint)
+        .collect();
+
+    let options: Vec<String> = SUMMARY_ACTIONS.iter().map(|a| a.to_string()).collect();
+    let menu = Menu {
+        prompt: "New issue",
+        options: &options,
+        mesg: Some(&mesg),
+        keys: &back,
+        ..Default::default()
+    };
+
+    Ok(match rofi::menu(&menu)? {
+        None => Outcome::Cancel,
+        Some(selection) if selection.custom_key.is_some() => Outcome::Back,
+        Some(selection) => match selection.index() {
+            0 => Outcome::Submit,
+            1 => Outcome::Next,
+            _ => Outcome::Cancel,
+        },
+    })
+}
+
+/// Pick the field to change from the summary screen
+fn edit_field(draft: &Draft) -> Result<Option<Step>> {
+    let team = draft
+        .team
+        .as_ref()
+        .map(|(alias, team)| format!("{} ({})", alias, team.name))
+        .unwrap_or_default();
+    let options = vec![
+        format!("Team: {}", team),
+        format!("Title: {}", draft.title),
+        format!(
+            "Description: {}",
+            draft.description.as_deref().unwrap_or("(none)")
+        ),
+    ];
+
+    let fields = [Step::Team, Step::Title, Step::Description];
+    Ok(rofi::select("Edit field", &options)?.map(|idx| fields[idx]))
+}
@@ -0,0 +1,149 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+use std::time::Duration;
+
+use crate::cache;
+use crate::commands::issues;
+use crate::config;
+use crate::icons;
+use crate::launcher::{Kind, Menu};
+use crate::linear::{IssueSummary, LinearClient};
+use crate::markup;
+use crate::rofi;
+use crate::script::{self, Retv, Row};
+
+/// How long search results are reused for a repeated query
+const CACHE_TTL: Duration = Duration::from_secs(120);
+
+/// Queries shorter than this are not sent to Linear
+const MIN_QUERY_LEN: usize = 2;
+
+/// Launch rofi with this binary as a script-mode provider
+///
+/// Other launchers have no script mode, so they prompt for a query and then
+/// list its results instead.
+pub async fn run() -> Result<()> {
+    if rofi::kind() != Kind::Rofi {
+        return prompt_and_search().await;
+    }
+
+    let exe = std::env::current_exe().context("Failed to locate rofi-linear binary")?;
+    // rofi appends the typed query, which may start with a dash
+    let modi = format!("search:{} search --script --", exe.display());
+
+    Command::new("rofi")
+        .args(["-show", "search", "-modi", &modi])
+        .args(config::load_config()?.rofi.args("search", None))
+        .status()
+        .context("Failed to run rofi")?;
+
+    Ok(())
+}
+
+/// Handle one step of rofi's script-mode protocol
+///
+/// rofi calls us with no entry when the mode opens, with the typed text each
+/// time a query is entered, and with the picked row otherwise. It doesn't
+/// call us per keystroke, so while typing rofi narrows the current results
+/// itself and every entered query re-runs the search, served from the cache
+/// when it was asked recently.
+pub async fn script(entry: Option<String>) -> Result<()> {
+    // A result row was picked: open it and let rofi close
+    if script::retv() == Retv::Selected {
+        if let Some(url) = script::info() {
+            open::that(url).ok();
+        }
+        return Ok(());
+    }
+
+    script::option("prompt", "Search");
+    script::option("markup-rows", "true");
+
+    let query = entry.unwrap_or_default();
+    let query = query.trim();
+    if query.chars().count() < MIN_QUERY_LEN {
+        script::option(
+            "message",
+            "Type a query and press Enter, then keep typing to narrow the results",
+        );
+        return Ok(());
+    }
+
+    match search(query).await {
+        Ok(issues) if issues.is_empty() => {
+            script::option(
+                "message",
+                &format!("No issues match '{}'", markup::escape(query)),
+            );
+        }
+        Ok(issues) => {
+            script::option(
+                "message",
+                &format!(
+                    "{} results for '{}'; type to narrow them, Ctrl+Enter to search again",
+                    issues.len(),
+                    markup::escape(query)
+                ),
+            );
+            let show_icons = config::load_config()?.icons;
+            for issue in issues {
+                let text = issues::format_row(&issue, !show_icons);
+                let icon = show_icons
+                    .then(|| icons::priority(issue.priority))
+                    .flatten();
+                let mut row = Row::new(&text).info(&issue.url);
+                row.icon = icon.as_deref();
+                row.print();
+            }
+        }
+        Err(e) => script::option(
+            "message",
+            &format!("Search failed: {}", markup::escape(&e.to_string())),
+        ),
+    }
+
+    Ok(())
+}
+
+async fn prompt_and_search() -> Result<()> {
+    let query = match rofi::input("Search", "Search issues...")? {
+        Some(q) => q,
+        None => return Ok(()), // User cancelled
+    };
+
+    let issues = search(&query).await?;
+    if issues.is_empty() {
+        rofi::error(&format!("No issues match '{}'", query))?;
+        return Ok(());
+    }
+
+    let rows = issues::Rows::from_issues(&issues)?;
+    let menu = Menu {
+        prompt: "Issue",
+        options: &rows.options,
+        markup: true,
+        icons: &rows.icons,
+        ..Default::default()
+    };
+    if let Some(selection) = rofi::menu(&menu)? {
+        open::that(&issues[selection.index()].url).context("Failed to open browser")?;
+    }
+
+    Ok(())
+}
+
+async fn search(query: &str) -> Result<Vec<IssueSummary>> {
+    let key = query.to_lowercase();
+    if let Some(issues) = cache::get("search", &key, CACHE_TTL) {
+        return Ok(issues);
+    }
+
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let client = LinearClient::new(&api_key);
+    let issues = client.search_issues(query).await?;
+    cache::set("search", &key, &issues, CACHE_TTL).ok();
+
+    Ok(issues)
+}
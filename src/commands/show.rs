@@ -0,0 +1,121 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::clipboard;
+use crate::commands::{assign, comment, issues, status};
+use crate::config;
+use crate::launcher::Menu;
+use crate::linear::{self, IssueDetails, IssueSummary, LinearClient};
+use crate::markup;
+use crate::rofi;
+
+/// Description lines shown in the message bar
+const DESCRIPTION_LINES: usize = 8;
+
+const ACTIONS: [&str; 5] = ["Open", "Copy link", "Comment", "Change state", "Assign"];
+
+pub async fn run(identifier: Option<String>) -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let client = LinearClient::new(&api_key);
+    match issues::pick(&client, identifier.as_deref()).await? {
+        Some(issue) => show(&client, &issue).await,
+        None => Ok(()), // User cancelled
+    }
+}
+
+/// Show an issue's details with rows for the actions available on it
+///
+/// Actions that change the issue return to the refreshed view; opening or
+/// copying the link closes it.
+pub async fn show(client: &LinearClient, issue: &IssueSummary) -> Result<()> {
+    loop {
+        let details = client.get_issue_details(&issue.id).await?;
+        let options: Vec<String> = ACTIONS.iter().map(|a| a.to_string()).collect();
+
+        let mesg = render(&details);
+        let menu = Menu {
+            id: Some("show"),
+            prompt: &details.summary.identifier,
+            options: &options,
+            mesg: Some(&mesg),
+            ..Default::default()
+        };
+        let selected = rofi::menu(&menu)?.map(|s| s.index());
+
+        let issue = &details.summary;
+        match selected {
+            Some(0) => {
+                open::that(&issue.url).context("Failed to open browser")?;
+                return Ok(());
+            }
+            Some(1) => {
+                clipboard::copy(&issue.url)?;
+                Command::new("notify-send")
+                    .args(["Linear", &format!("Copied {}", issue.url)])
+                    .spawn()
+                    .ok();
+                return Ok(());
+            }
+            Some(2) => comment::apply(client, issue).await?,
+            Some(3) => status::apply(client, issue).await?,
+            Some(4) => {
+                assign::apply(client, issue).await?;
+            }
+            _ => return Ok(()), // User cancelled
+        }
+    }
+}
+
+/// Render the issue as Pango markup for rofi's message bar
+pub fn render(details: &IssueDetails) -> String {
+    let issue = &details.summary;
+    let mut lines = vec![format!("<b>{}</b>", markup::escape(&issue.title))];
+
+    let mut field = |name: &str, value: &str| {
+        lines.push(format!("<b>{}:</b> {}", name, markup::escape(value)));
+    };
+
+    field("State", &issue.state.name);
+    field(
+        "Assignee",
+        issue
+            .assignee
+            .as_ref()
+            .map(|a| a.name.as_str())
+            .unwrap_or("Unassigned"),
+    );
+    field("Priority", linear::priority_name(issue.priority));
+
+    if !details.labels.nodes.is_empty() {
+        let labels: Vec<&str> = details
+            .labels
+            .nodes
+            .iter()
+            .map(|l| l.name.as_str())
+            .collect();
+        field("Labels", &labels.join(", "));
+    }
+    if let Some(project) = &details.project {
+        field("Project", &project.name);
+    }
+    if let Some(cycle) = &details.cycle {
+        let name = match &cycle.name {
+            Some(name) => format!("{} ({})", cycle.number, name),
+            None => cycle.number.to_string(),
+        };
+        field("Cycle", &name);
+    }
+
+    if let Some(description) = details.description.as_deref() {
+        let rendered = markup::from_markdown(description, DESCRIPTION_LINES);
+        if !rendered.is_empty() {
+            lines.push(String::new());
+            lines.push(rendered);
+        }
+    }
+
+    lines.join("\n")
+}
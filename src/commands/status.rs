@@ -0,0 +1,74 @@
+use anyhow::{Context, Result};
+use std::process::Command;
+
+use crate::commands::issues;
+use crate::config;
+use crate::launcher::Menu;
+use crate::linear::{IssueSummary, IssueUpdate, LinearClient};
+use crate::rofi;
+
+pub async fn run(identifier: Option<String>) -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let client = LinearClient::new(&api_key);
+    match issues::pick(&client, identifier.as_deref()).await? {
+        Some(issue) => apply(&client, &issue).await,
+        None => Ok(()), // User cancelled
+    }
+}
+
+/// Pick a new workflow state for an issue and apply it
+pub async fn apply(client: &LinearClient, issue: &IssueSummary) -> Result<()> {
+    let states = client.get_workflow_states(&issue.team.id).await?;
+    let options: Vec<String> = states
+        .iter()
+        .map(|s| {
+            if s.id == issue.state.id {
+                format!("{} (current)", s.name)
+            } else {
+                s.name.clone()
+            }
+        })
+        .collect();
+
+    let prompt = format!("{} state", issue.identifier);
+    let menu = Menu {
+        id: Some("state"),
+        prompt: &prompt,
+        options: &options,
+        ..Default::default()
+    };
+    let state = match rofi::menu(&menu)? {
+        Some(selection) => &states[selection.index()],
+        None => return Ok(()), // User cancelled
+    };
+
+    if state.id == issue.state.id {
+        return Ok(());
+    }
+
+    let update = IssueUpdate {
+        state_id: Some(state.id.clone()),
+        ..Default::default()
+    };
+
+    if let Err(e) = client.update_issue(&issue.id, &update).await {
+        Command::new("notify-send")
+            .args(["Linear", &format!("Failed to update issue: {}", e)])
+            .spawn()
+            .ok();
+        return Err(e);
+    }
+
+    Command::new("notify-send")
+        .args([
+            "Linear",
+            &format!("{} moved to {}", issue.identifier, state.name),
+        ])
+        .spawn()
+        .ok();
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use anyhow::{Context, Result};
+use chrono::{Duration, Utc};
+use serde_json::json;
+use std::process::Command;
+
+use crate::commands::{assign, issues, show};
+use crate::config;
+use crate::launcher::Menu;
+use crate::linear::{IssueSummary, IssueUpdate, LinearClient, WorkflowState};
+use crate::rofi;
+
+const ACTIONS: [&str; 6] = [
+    "Accept",
+    "Decline",
+    "Mark duplicate",
+    "Assign",
+    "Snooze",
+    "Skip",
+];
+
+const SNOOZE: [(&str, i64); 3] = [("1 day", 1), ("3 days", 3), ("1 week", 7)];
+
+pub async fn run(team: Option<String>) -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let (_alias, team_config) = config::get_team(team.as_deref())?.context("Team not found")?;
+
+    let client = LinearClient::new(&api_key);
+    let filter = json!({
+        "team": { "id": { "eq": team_config.id } },
+        "state": { "type": { "eq": "triage" } }
+    });
+    let queue = client.get_filtered_issues(&filter).await?;
+
+    if queue.is_empty() {
+        rofi::error(&format!("{} triage is empty.", team_config.name))?;
+        return Ok(());
+    }
+
+    let states = client.get_workflow_states(&team_config.id).await?;
+    let options: Vec<String> = ACTIONS.iter().map(|a| a.to_string()).collect();
+    let total = queue.len();
+    // Issues that got a decision, not counting skipped ones
+    let mut triaged = 0;
+
+    for (position, issue) in queue.iter().enumerate() {
+        let prompt = format!("Triage {}/{}", position + 1, total);
+        let mut mesg = show::render(&client.get_issue_details(&issue.id).await?);
+
+        // Stay on this issue until a decision goes through
+        loop {
+            let menu = Menu {
+                id: Some("triage"),
+                prompt: &prompt,
+                options: &options,
+                mesg: Some(&mesg),
+                ..Default::default()
+            };
+            let action = match rofi::menu(&menu)? {
+                Some(selection) => selection.index(),
+                None => return Ok(()), // User cancelled
+            };
+
+            let decided = match action {
+                0 => accept(&client, issue, &states).await?,
+                1 => decline(&client, issue, &states).await?,
+                2 => duplicate(&client, issue, &states).await?,
+                3 => {
+                    // Assigning leaves the issue in triage, so show it again
+                    // with its new assignee for a decision
+                    if assign::apply(&client, issue).await? {
+                        let details = client.get_issue_details(&issue.id).await?;
+                        mesg = show::render(&details);
+                    }
+                    false
+                }
+                4 => snooze(&client, issue).await?,
+                _ => break, // Skip
+            };
+
+            if decided {
+                triaged += 1;
+                break;
+            }
+        }
+    }
+
+    Command::new("notify-send")
+        .args([
+            "Linear",
+            &format!("Triaged {} of {} issues", triaged, total),
+        ])
+        .spawn()
+        .ok();
+
+    Ok(())
+}
+
+async fn update(client: &LinearClient, issue: &IssueSummary, update: IssueUpdate) -> Result<()> {
+    if let Err(e) = client.update_issue(&issue.id, &update).await {
+        Command::new("notify-send")
+            .args(["Linear", &format!("Failed to update issue: {}", e)])
+            .spawn()
+            .ok();
+        return Err(e);
+    }
+    Ok(())
+}
+
+/// Move the issue out of triage into a state of the user's choosing
+async fn accept(
+    client: &LinearClient,
+    issue: &IssueSummary,
+    states: &[WorkflowState],
+) -> Result<bool> {
+    let targets: Vec<&WorkflowState> = states.iter().filter(|s| s.state_type != "triage").collect();
+    let options: Vec<String> = targets.iter().map(|s| s.name.clone()).collect();
+
+    let prompt = format!("Accept {} into", issue.identifier);
+    let menu = Menu {
+        id: Some("accept"),
+        prompt: &prompt,
+        options: &options,
+        ..Default::default()
+    };
+    let state = match rofi::menu(&menu)? {
+        Some(selection) => targets[selection.index()],
+        None => return Ok(false),
+    };
+
+    let state_id = Some(state.id.clone());
+    update(
+        client,
+        issue,
+        IssueUpdate {
+            state_id,
+            ..Default::default()
+        },
+    )
+    .await?;
+    Ok(true)
+}
+
+/// The canceled state to use, preferring one named `preferred`
+fn canceled_state<'a>(states: &'a [WorkflowState], preferred: &str) -> Option<&'a WorkflowState> {
+    let canceled: Vec<&WorkflowState> = states
+        .iter()
+        .filter(|s| s.state_type == "canceled")
+        .collect();
+
+    canceled
+        .iter()
+        .find(|s| s.name.eq_ignore_ascii_case(preferred))
+        .or(canceled.first())
+        .copied()
+}
+
+async fn decline(
+    client: &LinearClient,
+    issue: &IssueSummary,
+    states: &[WorkflowState],
+) -> Result<bool> {
+    let state = canceled_state(states, "Canceled").context("Team has no canceled state")?;
+
+    let state_id = Some(state.id.clone());
+    update(
+        client,
+        issue,
+        IssueUpdate {
+            state_id,
+            ..Default::default()
+        },
+    )
+    .await?;
+    Ok(true)
+}
+
+async fn duplicate(
+    client: &LinearClient,
+    issue: &IssueSummary,
+    states: &[WorkflowState],
+) -> Result<bool> {
+    let original = match issues::search(client).await? {
+        Some(original) if original.id != issue.id => original,
+        _ => return Ok(false),
+    };
+
+    client.mark_duplicate(&issue.id, &original.id).await?;
+
+    if let Some(state) = canceled_state(states, "Duplicate") {
+        let state_id = Some(state.id.clone());
+        update(
+            client,
+            issue,
+            IssueUpdate {
+                state_id,
+                ..Default::default()
+            },
+        )
+        .await?;
+    }
+    Ok(true)
+}
+
+async fn snooze(client: &LinearClient, issue: &IssueSummary) -> Result<bool> {
+    let options: Vec<String> = SNOOZE.iter().map(|(name, _)| name.to_string()).collect();
+
+    let days = match rofi::select("Snooze for", &options)? {
+        Some(idx) => SNOOZE[idx].1,
+        None => return Ok(false),
+    };
+
+    let snoozed_until_at = Some(Utc::now() + Duration::days(days));
+    update(
+        client,
+        issue,
+        IssueUpdate {
+            snoozed_until_at,
+            ..Default::default()
+        },
+    )
+    .await?;
+    Ok(true)
+}
@@ -1,7 +1,7 @@
-use anyhow::{Context, Result};
-use std::io::{self, Write};
+use anyhow::Result;
 
 use crate::config;
+use crate::rofi;
 
 pub async fn run(team: Option<String>) -> Result<()> {
     let alias = match team {
@@ -14,27 +14,14 @@ pub async fn run(team: Option<String>) -> Result<()> {
                 return Ok(());
             }
 
-            println!("Linked teams:");
-            for (i, (alias, team)) in teams.iter().enumerate() {
-                println!("  {}. {} ({})", i + 1, alias, team.name);
+            let options: Vec<String> = teams
+                .iter()
+                .map(|(alias, team)| format!("{} ({})", alias, team.name))
+                .collect();
+            match rofi::select("Unlink team", &options)? {
+                Some(idx) => teams[idx].0.clone(),
+                None => return Ok(()), // User cancelled
             }
-            println!();
-
-            print!("Select team to unlink (1-{}): ", teams.len());
-            io::stdout().flush()?;
-
-            let mut selection = String::new();
-            io::stdin().read_line(&mut selection)?;
-            let selection: usize = selection
-                .trim()
-                .parse()
-                .context("Invalid selection")?;
-
-            if selection < 1 || selection > teams.len() {
-                anyhow::bail!("Invalid selection");
-            }
-
-            teams[selection - 1].0.clone()
         }
     };
 
@@ -0,0 +1,53 @@
+use anyhow::{Context, Result};
+
+use crate::commands::issues;
+use crate::config;
+use crate::linear::LinearClient;
+use crate::rofi;
+
+pub async fn run(name: Option<String>) -> Result<()> {
+    // Check for API key
+    let api_key =
+        config::get_api_key()?.context("No API key found. Run 'rofi-linear auth' first.")?;
+
+    let client = LinearClient::new(&api_key);
+    let views = client.get_custom_views().await?;
+
+    if views.is_empty() {
+        rofi::error("No custom views found in your Linear workspace.")?;
+        return Ok(());
+    }
+
+    // Match the name case-insensitively, falling back to the picker
+    let matched = name
+        .as_deref()
+        .and_then(|name| views.iter().position(|v| v.name.eq_ignore_ascii_case(name)));
+
+    let idx = match matched {
+        Some(idx) => idx,
+        None => {
+            let options: Vec<String> = views
+                .iter()
+                .map(|v| match &v.description {
+                    Some(d) if !d.is_empty() => format!("{} - {}", v.name, d),
+                    _ => v.name.clone(),
+                })
+                .collect();
+
+            match rofi::select("View", &options)? {
+                Some(idx) => idx,
+                None => return Ok(()), // User cancelled
+            }
+        }
+    };
+
+    let view = &views[idx];
+    let results = client.get_filtered_issues(&view.filter_data).await?;
+
+    if results.is_empty() {
+        rofi::error(&format!("No issues in '{}'.", view.name))?;
+        return Ok(());
+    }
+
+    issues::browse(&client, &view.name, &results).await
+}
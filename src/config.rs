@@ -4,11 +4,79 @@ use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 
+use crate::keys::Keys;
+use crate::launcher::Kind;
+use crate::theme::Theme;
+
+/// Settings left at their defaults aren't written back by `save_config`, so
+/// a later release can change the defaults of a config that never set them
 #[derive(Debug, Serialize, Deserialize, Default)]
 pub struct Config {
     pub default_team: Option<String>,
     #[serde(default)]
     pub teams: HashMap<String, TeamConfig>,
+    /// What `run` does with the text when the title names an existing issue
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub append_mode: AppendMode,
+    /// Launcher used for prompts, rofi unless set
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub launcher: Option<Kind>,
+    /// Custom keybindings per list
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub keys: Keys,
+    /// Show bundled priority icons in issue rows instead of glyphs (rofi only)
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub icons: bool,
+    /// rofi theme, extra arguments and per-prompt overrides
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub rofi: Theme,
+    /// Earlier titles offered in `run`'s title prompt
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub title_history: TitleHistory,
+}
+
+fn is_default<T: Default + PartialEq>(value: &T) -> bool {
+    *value == T::default()
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct TitleHistory {
+    /// How many titles to offer; 0 turns the history off
+    #[serde(
+        default = "default_title_history_size",
+        skip_serializing_if = "is_default_title_history_size"
+    )]
+    pub size: usize,
+    /// Only offer titles of issues created in the same team
+    #[serde(default, skip_serializing_if = "is_default")]
+    pub per_team: bool,
+}
+
+impl Default for TitleHistory {
+    fn default() -> Self {
+        TitleHistory {
+            size: default_title_history_size(),
+            per_team: false,
+        }
+    }
+}
+
+fn default_title_history_size() -> usize {
+    20
+}
+
+fn is_default_title_history_size(size: &usize) -> bool {
+    *size == default_title_history_size()
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum AppendMode {
+    /// Post the text as a new comment
+    #[default]
+    Comment,
+    /// Append the text to the issue's description
+    Description,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -132,9 +200,52 @@ pub fn get_team(alias: Option<&str>) -> Result<Option<(String, TeamConfig)>> {
 }
 
 pub fn list_teams() -> Result<Vec<(String, TeamConfig)>> {
+    let config = load_config()?;
+    let mut teams: Vec<(String, TeamConfig)> = config.teams.into_iter().collect();
+    teams.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(teams)
+}
+
+/// Find the alias a team was linked under
+pub fn alias_for_team_id(team_id: &str) -> Result<Option<String>> {
     let config = load_config()?;
     Ok(config
         .teams
         .into_iter()
-        .collect())
+        .find(|(_, t)| t.id == team_id)
+        .map(|(alias, _)| alias))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keys::IssueAction;
+
+    #[test]
+    fn defaults_are_not_written_back() {
+        let config: Config = serde_yaml::from_str(
+            "default_team: eng\nteams:\n  eng:\n    id: t1\n    name: Engineering\n",
+        )
+        .unwrap();
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(
+            yaml,
+            "default_team: eng\nteams:\n  eng:\n    id: t1\n    name: Engineering\n"
+        );
+    }
+
+    #[test]
+    fn only_changed_settings_are_written_back() {
+        let config: Config = serde_yaml::from_str(
+            "keys:\n  issues:\n    Alt+o: open\nrofi:\n  theme: card\ntitle_history:\n  per_team: true\n",
+        )
+        .unwrap();
+        assert_eq!(config.keys.issues["Alt+o"], IssueAction::Open);
+
+        let yaml = serde_yaml::to_string(&config).unwrap();
+        assert_eq!(
+            yaml,
+            "default_team: null\nteams: {}\nkeys:\n  issues:\n    Alt+o: open\nrofi:\n  theme: card\ntitle_history:\n  per_team: true\n"
+        );
+    }
 }
@@ -0,0 +1,200 @@
+//! How often and how recently choices were picked, for ordering pickers
+//!
+//! Scores are kept per namespace (`teams`, `labels`, `assignees`) and keyed
+//! by whatever identifies a choice there.
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Use {
+    count: u32,
+    last_used: DateTime<Utc>,
+}
+
+type Store = HashMap<String, HashMap<String, Use>>;
+
+fn store_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .context("Could not find data directory")?
+        .join("rofi-linear");
+
+    if !data_dir.exists() {
+        fs::create_dir_all(&data_dir)?;
+    }
+
+    Ok(data_dir.join("frecency.json"))
+}
+
+fn load() -> Store {
+    // A missing or corrupt store just means no history yet
+    store_path()
+        .ok()
+        .and_then(|path| fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Note that `key` was picked in `namespace`
+pub fn record(namespace: &str, key: &str) -> Result<()> {
+    let mut store = load();
+    bump(&mut store, namespace, key, Utc::now());
+    fs::write(store_path()?, serde_json::to_string(&store)?)?;
+    Ok(())
+}
+
+fn bump(store: &mut Store, namespace: &str, key: &str, now: DateTime<Utc>) {
+    let entry = store
+        .entry(namespace.to_string())
+        .or_default()
+        .entry(key.to_string())
+        .or_insert(Use {
+            count: 0,
+            last_used: now,
+        });
+    entry.count += 1;
+    entry.last_used = now;
+}
+
+/// Picks weighted by age, so a burst of old use fades behind recent habits
+fn score(usage: &Use, now: DateTime<Utc>) -> f64 {
+    let weight = match (now - usage.last_used).num_days() {
+        ..=4 => 100.0,
+        5..=14 => 70.0,
+        15..=31 => 50.0,
+        32..=90 => 30.0,
+        _ => 10.0,
+    };
+    usage.count as f64 * weight
+}
+
+fn scores(store: &Store, namespace: &str, now: DateTime<Utc>) -> HashMap<String, f64> {
+    store
+        .get(namespace)
+        .into_iter()
+        .flatten()
+        .map(|(key, usage)| (key.clone(), score(usage, now)))
+        .collect()
+}
+
+/// Sort items by descending frecency, keeping the current order for ties
+pub fn sort<T>(namespace: &str, items: &mut [T], key: impl Fn(&T) -> &str) {
+    sort_by_scores(&scores(&load(), namespace, Utc::now()), items, key);
+}
+
+fn sort_by_scores<T>(scores: &HashMap<String, f64>, items: &mut [T], key: impl Fn(&T) -> &str) {
+    let score = |item: &T| scores.get(key(item)).copied().unwrap_or(0.0);
+    items.sort_by(|a, b| score(b).total_cmp(&score(a)));
+}
+
+/// Index of the most frecent item, if any has been picked before; the first
+/// one on a tie, as `sort` would order them
+pub fn best<T>(namespace: &str, items: &[T], key: impl Fn(&T) -> &str) -> Option<usize> {
+    best_by_scores(&scores(&load(), namespace, Utc::now()), items, key)
+}
+
+fn best_by_scores<T>(
+    scores: &HashMap<String, f64>,
+    items: &[T],
+    key: impl Fn(&T) -> &str,
+) -> Option<usize> {
+    items
+        .iter()
+        .enumerate()
+        .filter_map(|(i, item)| scores.get(key(item)).map(|s| (i, *s)))
+        .min_by(|(_, a), (_, b)| b.total_cmp(a))
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, TimeZone};
+
+    fn now() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2026, 3, 1, 12, 0, 0).unwrap()
+    }
+
+    /// A store with `(key, count, days since last use)` in `teams`
+    fn store(uses: &[(&str, u32, i64)]) -> Store {
+        let teams = uses
+            .iter()
+            .map(|&(key, count, days)| {
+                let last_used = now() - Duration::days(days);
+                (key.to_string(), Use { count, last_used })
+            })
+            .collect();
+        HashMap::from([("teams".to_string(), teams)])
+    }
+
+    fn sorted(store: &Store, items: &[&str]) -> Vec<String> {
+        let mut items: Vec<String> = items.iter().map(|i| i.to_string()).collect();
+        sort_by_scores(&scores(store, "teams", now()), &mut items, |i| i);
+        items
+    }
+
+    #[test]
+    fn weight_decays_with_age() {
+        let usage = |days| Use {
+            count: 2,
+            last_used: now() - Duration::days(days),
+        };
+        assert_eq!(score(&usage(0), now()), 200.0);
+        assert_eq!(score(&usage(4), now()), 200.0);
+        assert_eq!(score(&usage(5), now()), 140.0);
+        assert_eq!(score(&usage(14), now()), 140.0);
+        assert_eq!(score(&usage(15), now()), 100.0);
+        assert_eq!(score(&usage(31), now()), 100.0);
+        assert_eq!(score(&usage(32), now()), 60.0);
+        assert_eq!(score(&usage(90), now()), 60.0);
+        assert_eq!(score(&usage(91), now()), 20.0);
+    }
+
+    #[test]
+    fn recent_use_outranks_old_bursts() {
+        // 3 uses today (300) beat 10 uses four months ago (100) and 5 uses
+        // a month ago (250)
+        let store = store(&[("old", 10, 120), ("month", 5, 20), ("now", 3, 0)]);
+        assert_eq!(
+            sorted(&store, &["old", "month", "now", "never"]),
+            ["now", "month", "old", "never"]
+        );
+    }
+
+    #[test]
+    fn ties_and_unused_keep_their_order() {
+        let store = store(&[("b", 1, 0), ("d", 1, 0)]);
+        assert_eq!(sorted(&store, &["a", "b", "c", "d"]), ["b", "d", "a", "c"]);
+        assert_eq!(sorted(&Store::new(), &["c", "a", "b"]), ["c", "a", "b"]);
+    }
+
+    #[test]
+    fn best_picks_the_first_top_score() {
+        let store = store(&[("b", 1, 0), ("d", 1, 0), ("a", 1, 100)]);
+        let scores = scores(&store, "teams", now());
+        assert_eq!(
+            best_by_scores(&scores, &["a", "b", "c", "d"], |i| i),
+            Some(1)
+        );
+        assert_eq!(best_by_scores(&scores, &["c", "e"], |i| i), None);
+    }
+
+    #[test]
+    fn bump_counts_uses_per_namespace() {
+        let mut store = Store::new();
+        let earlier = now() - Duration::days(10);
+        bump(&mut store, "teams", "eng", earlier);
+        bump(&mut store, "teams", "eng", now());
+        bump(&mut store, "labels", "eng", now());
+
+        let eng = &store["teams"]["eng"];
+        assert_eq!(eng.count, 2);
+        assert_eq!(eng.last_used, now());
+        assert_eq!(store["labels"]["eng"].count, 1);
+        assert!(scores(&store, "assignees", now()).is_empty());
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum Action {
+    Created,
+    Viewed,
+}
+
+/// One line of the history log
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct Entry {
+    pub timestamp: DateTime<Utc>,
+    pub action: Action,
+    /// Alias of the linked team, if the issue belongs to one
+    #[serde(default)]
+    pub team: Option<String>,
+    pub identifier: String,
+    pub url: String,
+    pub title: String,
+}
+
+fn history_path() -> Result<PathBuf> {
+    let data_dir = dirs::data_dir()
+        .context("Could not find data directory")?
+        .join("rofi-linear");
+
+    if !data_dir.exists() {
+        fs::create_dir_all(&data_dir)?;
+    }
+
+    let path = data_dir.join("history.jsonl");
+
+    // Older versions kept the history next to the config
+    if !path.exists() {
+        if let Some(legacy) = dirs::config_dir().map(|d| d.join("rofi-linear/history.jsonl")) {
+            if legacy.exists() {
+                fs::rename(&legacy, &path).or_else(|_| fs::copy(&legacy, &path).map(|_| ()))?;
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+/// Append an entry to the history log
+pub fn record(
+    action: Action,
+    team: Option<&str>,
+    identifier: &str,
+    url: &str,
+    title: &str,
+) -> Result<()> {
+    let entry = Entry {
+        timestamp: Utc::now(),
+        action,
+        team: team.map(String::from),
+        identifier: identifier.to_string(),
+        url: url.to_string(),
+        title: title.to_string(),
+    };
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(history_path()?)?;
+    writeln!(file, "{}", serde_json::to_string(&entry)?)?;
+    Ok(())
+}
+
+/// Read every entry, oldest first, skipping lines that don't parse
+pub fn load() -> Result<Vec<Entry>> {
+    let path = history_path()?;
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let content = fs::read_to_string(&path)?;
+    Ok(content
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect())
+}
+
+/// Titles of created issues, newest first without repeats, from `team` only
+/// when given
+pub fn titles(limit: usize, team: Option<&str>) -> Result<Vec<String>> {
+    let mut titles: Vec<String> = Vec::new();
+    for entry in load()?.into_iter().rev() {
+        if titles.len() == limit {
+            break;
+        }
+        if entry.action != Action::Created || (team.is_some() && entry.team.as_deref() != team) {
+            continue;
+        }
+        if !titles.contains(&entry.title) {
+            titles.push(entry.title);
+        }
+    }
+    Ok(titles)
+}
+
+/// The most recently touched issues, newest first, one entry per issue
+pub fn recent(limit: usize) -> Result<Vec<Entry>> {
+    let mut recent: Vec<Entry> = Vec::new();
+    for entry in load()?.into_iter().rev() {
+        if !recent.iter().any(|e| e.identifier == entry.identifier) {
+            recent.push(entry);
+            if recent.len() == limit {
+                break;
+            }
+        }
+    }
+    Ok(recent)
+}
@@ -0,0 +1,47 @@
+//! Icons bundled into the binary
+//!
+//! rofi loads row icons from files, so they're written to the cache
+//! directory the first time they're needed.
+
+use anyhow::{Context, Result};
+use std::fs;
+use std::path::PathBuf;
+
+const PRIORITY: [(&str, &str); 5] = [
+    ("none", include_str!("../assets/icons/priority-none.svg")),
+    (
+        "urgent",
+        include_str!("../assets/icons/priority-urgent.svg"),
+    ),
+    ("high", include_str!("../assets/icons/priority-high.svg")),
+    (
+        "medium",
+        include_str!("../assets/icons/priority-medium.svg"),
+    ),
+    ("low", include_str!("../assets/icons/priority-low.svg")),
+];
+
+fn icon_path(name: &str, svg: &str) -> Result<PathBuf> {
+    let icon_dir = dirs::cache_dir()
+        .context("Could not find cache directory")?
+        .join("rofi-linear/icons");
+
+    if !icon_dir.exists() {
+        fs::create_dir_all(&icon_dir)?;
+    }
+
+    // Rewritten when a new version ships a different icon
+    let path = icon_dir.join(format!("{}.svg", name));
+    if fs::read_to_string(&path).ok().as_deref() != Some(svg) {
+        fs::write(&path, svg)?;
+    }
+
+    Ok(path)
+}
+
+/// Path to the icon for a Linear priority (0 = none ... 4 = low)
+pub fn priority(priority: u8) -> Option<String> {
+    let (name, svg) = PRIORITY.get(priority as usize)?;
+    let path = icon_path(&format!("priority-{}", name), svg).ok()?;
+    Some(path.to_string_lossy().into_owned())
+}
@@ -0,0 +1,295 @@
+//! Custom keybindings for alternate actions, configurable per list
+//!
+//! ```yaml
+//! keys:
+//!   issues:
+//!     Alt+1: open
+//!     Alt+2: copy-link
+//!     Alt+3: mark-done
+//!   create:
+//!     Alt+q: submit
+//!     Alt+Left: back
+//! ```
+//!
+//! Setting a list replaces its default bindings.
+
+use anyhow::{bail, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// rofi's `-kb-custom-1` to `-kb-custom-19`
+const CUSTOM_SLOTS: usize = 19;
+
+/// An action a key can be bound to
+pub trait Action: Copy {
+    /// Shown next to the binding in the key hints
+    fn label(self) -> &'static str;
+}
+
+/// Keys mapped to actions, e.g. `Alt+1` to `open`
+pub type Bindings<A> = BTreeMap<String, A>;
+
+/// `(binding, label)` pairs in the form the launcher takes
+pub fn hints<A: Action>(bindings: &Bindings<A>) -> Vec<(&str, &str)> {
+    bindings
+        .iter()
+        .map(|(key, action)| (key.as_str(), action.label()))
+        .collect()
+}
+
+/// The action for a custom key index returned by the launcher
+pub fn action<A: Action>(bindings: &Bindings<A>, custom_key: usize) -> Option<A> {
+    bindings.values().nth(custom_key).copied()
+}
+
+/// A binding in a form where spellings of the same key compare equal
+///
+/// Modifiers are case-insensitive and unordered (`alt+Ctrl+x` is
+/// `Control+Alt+x`), as are named keys like `Left`; single characters keep
+/// their case since `q` and `Q` are different keysyms.
+fn normalize(binding: &str) -> String {
+    let mut parts: Vec<&str> = binding.split('+').map(str::trim).collect();
+    let key = parts.pop().unwrap_or_default();
+    let mut modifiers: Vec<String> = parts
+        .iter()
+        .map(|m| match m.to_lowercase().as_str() {
+            "control" => "ctrl".to_string(),
+            m => m.to_string(),
+        })
+        .collect();
+    modifiers.sort();
+    modifiers.dedup();
+
+    let key = if key.chars().count() > 1 {
+        key.to_lowercase()
+    } else {
+        key.to_string()
+    };
+    modifiers.push(key);
+    modifiers.join("+")
+}
+
+/// Check that the `list` bindings fit rofi's custom key slots and bind no
+/// key twice, naming the action of an offending binding
+///
+/// Commands check the list they use, so a bad binding doesn't get in the way
+/// of the others. A binding can list several keys separated by commas, as
+/// rofi allows.
+pub fn validate<A: Action>(list: &str, bindings: &Bindings<A>) -> Result<()> {
+    if let Some((key, action)) = bindings.iter().nth(CUSTOM_SLOTS) {
+        bail!(
+            "keys.{}: too many bindings, rofi has only {} custom keys (\"{}\" for {} is one too many)",
+            list,
+            CUSTOM_SLOTS,
+            key,
+            action.label()
+        );
+    }
+
+    let mut seen: BTreeMap<String, (&str, A)> = BTreeMap::new();
+    for (binding, &action) in bindings {
+        for key in binding.split(',').filter(|k| !k.trim().is_empty()) {
+            if let Some((other, other_action)) = seen.insert(normalize(key), (binding, action)) {
+                bail!(
+                    "keys.{}: \"{}\" ({}) and \"{}\" ({}) bind the same key",
+                    list,
+                    other,
+                    other_action.label(),
+                    binding,
+                    action.label()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Actions on the rows of an issue list
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum IssueAction {
+    Open,
+    CopyLink,
+    CopyIdentifier,
+    /// Move to the team's first completed state
+    MarkDone,
+}
+
+impl Action for IssueAction {
+    fn label(self) -> &'static str {
+        match self {
+            IssueAction::Open => "Open in browser",
+            IssueAction::CopyLink => "Copy link",
+            IssueAction::CopyIdentifier => "Copy identifier",
+            IssueAction::MarkDone => "Mark done",
+        }
+    }
+}
+
+/// Actions in the inbox
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum InboxAction {
+    /// Mark the picked notification read without opening it
+    MarkRead,
+    MarkAllRead,
+}
+
+impl Action for InboxAction {
+    fn label(self) -> &'static str {
+        match self {
+            InboxAction::MarkRead => "Mark read",
+            InboxAction::MarkAllRead => "Mark all read",
+        }
+    }
+}
+
+/// Actions in the create wizard's prompts
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CreateAction {
+    /// Create the issue straight away, skipping the description
+    Submit,
+    /// Return to the previous step
+    Back,
+}
+
+impl Action for CreateAction {
+    fn label(self) -> &'static str {
+        match self {
+            CreateAction::Submit => "Create now",
+            CreateAction::Back => "Back",
+        }
+    }
+}
+
+/// Lists still on their defaults aren't serialized, so they keep following
+/// the defaults
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub struct Keys {
+    #[serde(default = "default_issues", skip_serializing_if = "is_default_issues")]
+    pub issues: Bindings<IssueAction>,
+    #[serde(default = "default_inbox", skip_serializing_if = "is_default_inbox")]
+    pub inbox: Bindings<InboxAction>,
+    #[serde(default = "default_create", skip_serializing_if = "is_default_create")]
+    pub create: Bindings<CreateAction>,
+}
+
+impl Default for Keys {
+    fn default() -> Self {
+        Keys {
+            issues: default_issues(),
+            inbox: default_inbox(),
+            create: default_create(),
+        }
+    }
+}
+
+fn bindings<A>(pairs: &[(&str, A)]) -> Bindings<A>
+where
+    A: Copy,
+{
+    pairs
+        .iter()
+        .map(|(key, action)| (key.to_string(), *action))
+        .collect()
+}
+
+fn default_issues() -> Bindings<IssueAction> {
+    bindings(&[
+        ("Alt+1", IssueAction::CopyLink),
+        ("Alt+2", IssueAction::CopyIdentifier),
+        ("Alt+3", IssueAction::Open),
+        ("Alt+4", IssueAction::MarkDone),
+    ])
+}
+
+fn default_inbox() -> Bindings<InboxAction> {
+    bindings(&[
+        ("Alt+1", InboxAction::MarkAllRead),
+        ("Alt+2", InboxAction::MarkRead),
+    ])
+}
+
+fn default_create() -> Bindings<CreateAction> {
+    bindings(&[
+        ("Alt+q", CreateAction::Submit),
+        ("Alt+Left", CreateAction::Back),
+    ])
+}
+
+fn is_default_issues(bindings: &Bindings<IssueAction>) -> bool {
+    *bindings == default_issues()
+}
+
+fn is_default_inbox(bindings: &Bindings<InboxAction>) -> bool {
+    *bindings == default_inbox()
+}
+
+fn is_default_create(bindings: &Bindings<CreateAction>) -> bool {
+    *bindings == default_create()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn defaults_are_valid() {
+        let keys = Keys::default();
+        validate("issues", &keys.issues).unwrap();
+        validate("inbox", &keys.inbox).unwrap();
+        validate("create", &keys.create).unwrap();
+    }
+
+    #[test]
+    fn rejects_more_bindings_than_rofi_slots() {
+        let bindings: Bindings<IssueAction> = (1..=20)
+            .map(|n| (format!("Alt+F{:02}", n), IssueAction::Open))
+            .collect();
+        let error = validate("issues", &bindings).unwrap_err().to_string();
+        assert!(error.contains("keys.issues"), "{}", error);
+        assert!(error.contains("Alt+F20"), "{}", error);
+        assert!(error.contains("Open in browser"), "{}", error);
+
+        let fits: Bindings<IssueAction> = bindings.into_iter().take(19).collect();
+        validate("issues", &fits).unwrap();
+    }
+
+    #[test]
+    fn rejects_the_same_key_spelled_twice() {
+        let error = validate(
+            "issues",
+            &bindings(&[
+                ("Alt+1", IssueAction::Open),
+                ("alt+1", IssueAction::MarkDone),
+            ]),
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(error.contains("Open in browser"), "{}", error);
+        assert!(error.contains("Mark done"), "{}", error);
+
+        for (a, b) in [
+            ("Ctrl+Alt+Left", "alt+control+left"),
+            ("Alt+q", "Alt+2,Alt+q"),
+        ] {
+            let pairs = [(a, CreateAction::Submit), (b, CreateAction::Back)];
+            assert!(
+                validate("create", &bindings(&pairs)).is_err(),
+                "{} {}",
+                a,
+                b
+            );
+        }
+    }
+
+    #[test]
+    fn keeps_character_case_apart() {
+        let pairs = [
+            ("Alt+q", CreateAction::Submit),
+            ("Alt+Q", CreateAction::Back),
+        ];
+        validate("create", &bindings(&pairs)).unwrap();
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::{Kind, Launcher, Menu, Prompt, Selection};
+use crate::markup;
+
+/// Arguments for `kind` showing `prompt` over `rows` lines; `free_text` allows
+/// entries that match no line
+///
+/// None of these launchers can mark several lines, so there's no multi-select
+/// flag; a multi-select picks one.
+fn args(kind: Kind, prompt: &str, free_text: bool, rows: usize) -> Vec<String> {
+    let mut args: Vec<String> = match kind {
+        Kind::Wofi => vec!["--dmenu".into(), "--prompt".into(), prompt.into()],
+        Kind::Fuzzel => vec!["--dmenu".into(), "--prompt".into(), format!("{}: ", prompt)],
+        Kind::Tofi => vec!["--prompt-text".into(), format!("{}: ", prompt)],
+        _ => vec!["-p".into(), prompt.into()],
+    };
+
+    if free_text && kind == Kind::Tofi {
+        args.push("--require-match=false".into());
+    }
+    // Hide fuzzel's empty list when there is nothing to suggest
+    if kind == Kind::Fuzzel && rows == 0 {
+        args.extend(["--lines".into(), "0".into()]);
+    }
+
+    args
+}
+
+/// Launchers that speak the plain dmenu protocol: options on stdin, the
+/// chosen line (or typed text) on stdout
+pub struct Dmenu {
+    kind: Kind,
+}
+
+impl Dmenu {
+    pub fn new(kind: Kind) -> Self {
+        Self { kind }
+    }
+
+    fn program(&self) -> &'static str {
+        match self.kind {
+            Kind::Wofi => "wofi",
+            Kind::Fuzzel => "fuzzel",
+            Kind::Tofi => "tofi",
+            Kind::Bemenu => "bemenu",
+            _ => "dmenu",
+        }
+    }
+
+    fn run(&self, args: &[String], lines: &[String]) -> Result<Option<String>> {
+        let program = self.program();
+        let mut child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .with_context(|| format!("Failed to spawn {}", program))?;
+
+        {
+            let stdin = child.stdin.as_mut().context("Failed to get stdin")?;
+            for line in lines {
+                writeln!(stdin, "{}", line)?;
+            }
+        }
+
+        let output = child
+            .wait_with_output()
+            .with_context(|| format!("Failed to wait for {}", program))?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let text = String::from_utf8(output.stdout)
+            .with_context(|| format!("Invalid UTF-8 from {}", program))?
+            .trim_end_matches('\n')
+            .to_string();
+
+        Ok(Some(text))
+    }
+}
+
+impl Launcher for Dmenu {
+    fn input(&self, prompt: &Prompt) -> Result<Option<String>> {
+        let args = args(self.kind, prompt.prompt, true, prompt.suggestions.len());
+        Ok(self
+            .run(&args, prompt.suggestions)?
+            .map(|t| t.trim().to_string()))
+    }
+
+    fn select(&self, menu: &Menu) -> Result<Option<Selection>> {
+        let args = args(self.kind, menu.prompt, false, menu.options.len());
+
+        // Map the chosen line back to its row; identical rows pick the first
+        let index = self
+            .run(&args, menu.options)?
+            .and_then(|chosen| menu.options.iter().position(|o| *o == chosen));
+
+        Ok(index.map(|index| Selection {
+            indices: vec![index],
+            custom_key: None,
+        }))
+    }
+
+    fn error(&self, message: &str) -> Result<()> {
+        // Notification daemons read the body as markup
+        Command::new("notify-send")
+            .args(["-u", "critical", "Linear", &markup::escape(message)])
+            .status()
+            .context("Failed to show error notification")?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    #[test]
+    fn wofi_args() {
+        let expected = strings(&["--dmenu", "--prompt", "Team"]);
+        assert_eq!(args(Kind::Wofi, "Team", false, 3), expected);
+        assert_eq!(args(Kind::Wofi, "Team", true, 0), expected);
+    }
+
+    #[test]
+    fn fuzzel_args() {
+        assert_eq!(
+            args(Kind::Fuzzel, "Team", false, 3),
+            strings(&["--dmenu", "--prompt", "Team: "])
+        );
+        assert_eq!(
+            args(Kind::Fuzzel, "Title", true, 2),
+            strings(&["--dmenu", "--prompt", "Title: "])
+        );
+        assert_eq!(
+            args(Kind::Fuzzel, "Title", true, 0),
+            strings(&["--dmenu", "--prompt", "Title: ", "--lines", "0"])
+        );
+    }
+
+    #[test]
+    fn tofi_args() {
+        assert_eq!(
+            args(Kind::Tofi, "Team", false, 3),
+            strings(&["--prompt-text", "Team: "])
+        );
+        assert_eq!(
+            args(Kind::Tofi, "Title", true, 0),
+            strings(&["--prompt-text", "Title: ", "--require-match=false"])
+        );
+    }
+
+    #[test]
+    fn bemenu_and_dmenu_args() {
+        for kind in [Kind::Bemenu, Kind::Dmenu] {
+            let expected = strings(&["-p", "Team"]);
+            assert_eq!(args(kind, "Team", false, 3), expected);
+            assert_eq!(args(kind, "Team", true, 0), expected);
+        }
+    }
+}
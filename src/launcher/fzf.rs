@@ -0,0 +1,318 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use super::{Launcher, Menu, Prompt, Selection};
+use crate::markup;
+
+/// fzf in the current terminal
+pub struct Fzf;
+
+/// Translate a rofi-style binding such as `Alt+1` to fzf's `alt-1`
+fn fzf_key(binding: &str) -> String {
+    binding
+        .to_lowercase()
+        .replace("control", "ctrl")
+        .replace('+', "-")
+}
+
+/// Arguments for picking from `menu`, whose rows carry their index in a
+/// hidden first column
+fn pick_args(menu: &Menu, multi: bool) -> Vec<String> {
+    let mut args: Vec<String> = vec![
+        "--prompt".into(),
+        format!("{}> ", menu.prompt),
+        "--delimiter".into(),
+        "\t".into(),
+        "--with-nth".into(),
+        "2..".into(),
+    ];
+
+    if multi {
+        args.push("--multi".into());
+    }
+    if let Some(row) = menu.selected {
+        // pos() counts from 1
+        args.push(format!("--bind=load:pos({})", row + 1));
+    }
+    if !menu.keys.is_empty() {
+        let keys: Vec<String> = menu.keys.iter().map(|(b, _)| fzf_key(b)).collect();
+        args.push(format!("--expect={}", keys.join(",")));
+    }
+
+    let mut header: Vec<String> = menu.mesg.iter().map(|m| markup::strip(m)).collect();
+    let hints: Vec<String> = menu
+        .keys
+        .iter()
+        .map(|(b, label)| format!("{}: {}", b, label))
+        .collect();
+    if !hints.is_empty() {
+        header.push(hints.join("  "));
+    }
+    if !header.is_empty() {
+        args.push("--header".into());
+        args.push(header.join("\n"));
+    }
+
+    args
+}
+
+/// Arguments for a text prompt offering its suggestions as rows, with `keys`
+/// that also submit it
+fn input_args(prompt: &Prompt, keys: &[(&str, &str)]) -> Vec<String> {
+    let mut header: Vec<String> = prompt.mesg.iter().map(|m| markup::strip(m)).collect();
+    header.push(prompt.placeholder.to_string());
+
+    let mut args: Vec<String> = vec![
+        "--print-query".into(),
+        "--prompt".into(),
+        format!("{}> ", prompt.prompt),
+    ];
+    if !prompt.suggestions.is_empty() {
+        // A row matching the query would otherwise win on Enter
+        args.push("--bind=ctrl-space:replace-query,alt-enter:print-query".into());
+        header.push("Ctrl+Space: Edit row  Alt+Enter: Use typed text".into());
+    }
+    if !keys.is_empty() {
+        let expect: Vec<String> = keys.iter().map(|(b, _)| fzf_key(b)).collect();
+        args.push(format!("--expect={}", expect.join(",")));
+        let hints: Vec<String> = keys
+            .iter()
+            .map(|(b, label)| format!("{}: {}", b, label))
+            .collect();
+        header.push(hints.join("  "));
+    }
+    args.extend(["--header".into(), header.join("\n")]);
+    if let Some(initial) = prompt.initial {
+        args.extend(["--query".into(), initial.to_string()]);
+    }
+
+    args
+}
+
+impl Fzf {
+    fn run(&self, args: &[String], lines: &[String]) -> Result<(Option<i32>, String)> {
+        let mut child = Command::new("fzf")
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .context("Failed to spawn fzf")?;
+
+        {
+            let stdin = child.stdin.as_mut().context("Failed to get stdin")?;
+            for line in lines {
+                writeln!(stdin, "{}", line)?;
+            }
+        }
+
+        let output = child.wait_with_output().context("Failed to wait for fzf")?;
+        let text = String::from_utf8(output.stdout).context("Invalid UTF-8 from fzf")?;
+        Ok((output.status.code(), text))
+    }
+
+    fn pick(&self, menu: &Menu, multi: bool) -> Result<Option<Selection>> {
+        let args = pick_args(menu, multi);
+        let rows: Vec<String> = menu
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, o)| format!("{}\t{}", i, o))
+            .collect();
+
+        let (code, output) = self.run(&args, &rows)?;
+        if code != Some(0) {
+            return Ok(None);
+        }
+
+        let mut lines = output.lines();
+
+        // With --expect the first line names the key, empty for Enter
+        let custom_key = if menu.keys.is_empty() {
+            None
+        } else {
+            let pressed = lines.next().unwrap_or_default();
+            menu.keys.iter().position(|(b, _)| fzf_key(b) == pressed)
+        };
+
+        let indices = lines
+            .filter_map(|l| l.split('\t').next())
+            .map(|i| i.parse::<usize>().context("Invalid index from fzf"))
+            .collect::<Result<Vec<_>>>()?;
+
+        if indices.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Selection {
+                indices,
+                custom_key,
+            }))
+        }
+    }
+}
+
+impl Launcher for Fzf {
+    fn input(&self, prompt: &Prompt) -> Result<Option<String>> {
+        Ok(self.input_with_keys(prompt, &[])?.map(|(text, _)| text))
+    }
+
+    fn input_with_keys(
+        &self,
+        prompt: &Prompt,
+        keys: &[(&str, &str)],
+    ) -> Result<Option<(String, Option<usize>)>> {
+        let args = input_args(prompt, keys);
+
+        // With nothing to match, accepting exits 1 but still prints the query.
+        // --expect adds a line naming the key, empty for Enter, and a picked
+        // suggestion comes last; print-query prints the query alone
+        let (code, output) = self.run(&args, prompt.suggestions)?;
+        match code {
+            Some(0) | Some(1) => {
+                let mut lines = output.lines();
+                let query = lines.next().unwrap_or_default();
+                let key = if keys.is_empty() {
+                    None
+                } else {
+                    let pressed = lines.next().unwrap_or_default();
+                    keys.iter().position(|(b, _)| fzf_key(b) == pressed)
+                };
+                let text = lines.next().unwrap_or(query);
+                Ok(Some((text.to_string(), key)))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn select(&self, menu: &Menu) -> Result<Option<Selection>> {
+        self.pick(menu, false)
+    }
+
+    fn multi_select(&self, menu: &Menu) -> Result<Option<Selection>> {
+        self.pick(menu, true)
+    }
+
+    fn error(&self, message: &str) -> Result<()> {
+        super::print_error(message);
+        Ok(())
+    }
+
+    fn supports_keys(&self) -> bool {
+        true
+    }
+
+    fn supports_message(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(args: &[&str]) -> Vec<String> {
+        args.iter().map(|a| a.to_string()).collect()
+    }
+
+    const ROWS: [&str; 6] = [
+        "--prompt",
+        "Team> ",
+        "--delimiter",
+        "\t",
+        "--with-nth",
+        "2..",
+    ];
+
+    #[test]
+    fn translates_keys() {
+        assert_eq!(fzf_key("Alt+1"), "alt-1");
+        assert_eq!(fzf_key("Control+Alt+q"), "ctrl-alt-q");
+        assert_eq!(fzf_key("Alt+Left"), "alt-left");
+    }
+
+    #[test]
+    fn plain_pick_args() {
+        let options = strings(&["eng", "ops"]);
+        let menu = Menu {
+            prompt: "Team",
+            options: &options,
+            ..Default::default()
+        };
+        assert_eq!(pick_args(&menu, false), strings(&ROWS));
+
+        let mut multi = strings(&ROWS);
+        multi.push("--multi".into());
+        assert_eq!(pick_args(&menu, true), multi);
+    }
+
+    #[test]
+    fn pick_args_with_selection_keys_and_message() {
+        let options = strings(&["eng", "ops"]);
+        let menu = Menu {
+            prompt: "Team",
+            options: &options,
+            mesg: Some("<b>Pick</b> &amp; go"),
+            keys: &[("Alt+1", "Copy link"), ("Alt+2", "Open")],
+            selected: Some(1),
+            ..Default::default()
+        };
+
+        let mut expected = strings(&ROWS);
+        expected.extend(strings(&[
+            "--multi",
+            "--bind=load:pos(2)",
+            "--expect=alt-1,alt-2",
+            "--header",
+            "Pick & go\nAlt+1: Copy link  Alt+2: Open",
+        ]));
+        assert_eq!(pick_args(&menu, true), expected);
+    }
+
+    fn prompt<'a>(initial: Option<&'a str>, suggestions: &'a [String]) -> Prompt<'a> {
+        Prompt {
+            id: None,
+            prompt: "Title",
+            placeholder: "Issue title...",
+            initial,
+            mesg: None,
+            suggestions,
+        }
+    }
+
+    #[test]
+    fn plain_input_args() {
+        assert_eq!(
+            input_args(&prompt(None, &[]), &[]),
+            strings(&[
+                "--print-query",
+                "--prompt",
+                "Title> ",
+                "--header",
+                "Issue title..."
+            ])
+        );
+    }
+
+    #[test]
+    fn input_args_with_suggestions_keys_and_initial() {
+        let suggestions = strings(&["Fix login"]);
+        assert_eq!(
+            input_args(
+                &prompt(Some("Draft"), &suggestions),
+                &[("Alt+Left", "Back"), ("Alt+q", "Create now")]
+            ),
+            strings(&[
+                "--print-query",
+                "--prompt",
+                "Title> ",
+                "--bind=ctrl-space:replace-query,alt-enter:print-query",
+                "--expect=alt-left,alt-q",
+                "--header",
+                "Issue title...\nCtrl+Space: Edit row  Alt+Enter: Use typed text\nAlt+Left: Back  Alt+q: Create now",
+                "--query",
+                "Draft",
+            ])
+        );
+    }
+}
@@ -0,0 +1,175 @@
+use anyhow::Result;
+use clap::ValueEnum;
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use crate::theme::Theme;
+
+mod dmenu;
+mod fzf;
+mod rofi;
+mod tty;
+
+/// The launchers rofi-linear can drive
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize, ValueEnum, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum Kind {
+    #[default]
+    Rofi,
+    Wofi,
+    Fuzzel,
+    Tofi,
+    Bemenu,
+    Dmenu,
+    /// fzf in the current terminal
+    Fzf,
+    /// Numbered prompts in the current terminal
+    Tty,
+}
+
+impl Kind {
+    /// Whether the launcher opens a window rather than using the terminal
+    pub fn graphical(self) -> bool {
+        !matches!(self, Kind::Fzf | Kind::Tty)
+    }
+}
+
+/// Whether there's an X11 or Wayland display to open launcher windows on
+pub fn has_display() -> bool {
+    ["DISPLAY", "WAYLAND_DISPLAY"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some_and(|v| !v.is_empty()))
+}
+
+/// Print an error for a terminal launcher on the terminal it prompts on, so
+/// it shows even with stderr redirected; stderr when there's no terminal
+fn print_error(message: &str) {
+    let printed = OpenOptions::new()
+        .write(true)
+        .open("/dev/tty")
+        .and_then(|mut tty| writeln!(tty, "Error: {}", message));
+    if printed.is_err() {
+        eprintln!("Error: {}", message);
+    }
+}
+
+/// A free-text prompt
+pub struct Prompt<'a> {
+    /// Key for the prompt's theme overrides when its text changes, e.g.
+    /// `comment` for "Comment on ENG-123"; the lowercased prompt otherwise
+    pub id: Option<&'a str>,
+    pub prompt: &'a str,
+    pub placeholder: &'a str,
+    /// Text the entry starts with
+    pub initial: Option<&'a str>,
+    /// Pango markup shown above the entry
+    pub mesg: Option<&'a str>,
+    /// Earlier entries offered as rows; typed text is still accepted
+    pub suggestions: &'a [String],
+}
+
+/// The key theme overrides are looked up by
+fn style_id(id: Option<&str>, prompt: &str) -> String {
+    id.map_or_else(|| prompt.to_lowercase(), str::to_string)
+}
+
+impl Prompt<'_> {
+    pub fn style_id(&self) -> String {
+        style_id(self.id, self.prompt)
+    }
+}
+
+impl Menu<'_> {
+    pub fn style_id(&self) -> String {
+        style_id(self.id, self.prompt)
+    }
+}
+
+/// A list to pick from
+#[derive(Default)]
+pub struct Menu<'a> {
+    /// Key for the menu's theme overrides when its prompt changes, e.g.
+    /// `assign` for "Assign ENG-123"; the lowercased prompt otherwise
+    pub id: Option<&'a str>,
+    pub prompt: &'a str,
+    pub options: &'a [String],
+    /// Whether options are Pango markup
+    pub markup: bool,
+    /// Icon file per option; empty for none
+    pub icons: &'a [String],
+    /// Pango markup shown above the list
+    pub mesg: Option<&'a str>,
+    /// `(binding, label)` pairs for alternate actions, e.g. `("Alt+1", "Copy link")`
+    pub keys: &'a [(&'a str, &'a str)],
+    /// Row highlighted when the menu opens, where the launcher supports it
+    pub selected: Option<usize>,
+}
+
+/// Entries picked from a list, along with the custom key used to pick them
+pub struct Selection {
+    /// Picked rows; more than one only with multi-select
+    pub indices: Vec<usize>,
+    /// Index into the menu's `keys`, `None` for Enter
+    pub custom_key: Option<usize>,
+}
+
+impl Selection {
+    /// The first picked row
+    pub fn index(&self) -> usize {
+        self.indices[0]
+    }
+}
+
+/// A program that can prompt for text and pick from lists
+///
+/// Backends report what they can't do so the caller can fall back, e.g. to
+/// a follow-up menu when custom keys aren't available.
+pub trait Launcher: Send + Sync {
+    /// Prompt for text; `Some("")` is an empty submission, `None` a cancel
+    fn input(&self, prompt: &Prompt) -> Result<Option<String>>;
+
+    /// Prompt for text with `(binding, label)` keys that also submit it,
+    /// returning the index of the key used; without key support only Enter
+    /// submits
+    fn input_with_keys(
+        &self,
+        prompt: &Prompt,
+        _keys: &[(&str, &str)],
+    ) -> Result<Option<(String, Option<usize>)>> {
+        Ok(self.input(prompt)?.map(|text| (text, None)))
+    }
+
+    fn select(&self, menu: &Menu) -> Result<Option<Selection>>;
+
+    /// Pick several rows; backends without multi-select pick one
+    fn multi_select(&self, menu: &Menu) -> Result<Option<Selection>> {
+        self.select(menu)
+    }
+
+    fn error(&self, message: &str) -> Result<()>;
+
+    /// Whether `Menu::keys` are bound to keys
+    fn supports_keys(&self) -> bool {
+        false
+    }
+
+    /// Whether `mesg` is displayed
+    fn supports_message(&self) -> bool {
+        false
+    }
+
+    /// Whether options can be Pango markup
+    fn supports_markup(&self) -> bool {
+        false
+    }
+}
+
+pub fn build(kind: Kind, theme: Theme) -> Box<dyn Launcher> {
+    match kind {
+        Kind::Rofi => Box::new(rofi::Rofi { theme }),
+        Kind::Fzf => Box::new(fzf::Fzf),
+        Kind::Tty => Box::new(tty::Tty::default()),
+        other => Box::new(dmenu::Dmenu::new(other)),
+    }
+}
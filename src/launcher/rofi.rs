@@ -0,0 +1,254 @@
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, ExitStatus, Stdio};
+
+use super::{Launcher, Menu, Prompt, Selection};
+use crate::markup;
+use crate::theme::Theme;
+
+/// Rows of suggestions shown under a text prompt; more are reached by scrolling
+const MAX_SUGGESTION_ROWS: usize = 8;
+
+pub struct Rofi {
+    pub theme: Theme,
+}
+
+impl Rofi {
+    fn dmenu_select(&self, menu: &Menu, multi: bool) -> Result<Option<Selection>> {
+        let mut args: Vec<String> = ["-dmenu", "-p", menu.prompt, "-format", "i"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        args.extend(self.theme.args(&menu.style_id(), None));
+
+        if multi {
+            args.push("-multi-select".to_string());
+        }
+        if menu.markup {
+            args.push("-markup-rows".to_string());
+        }
+        if let Some(row) = menu.selected {
+            args.push("-selected-row".to_string());
+            args.push(row.to_string());
+        }
+        if !menu.icons.is_empty() {
+            args.push("-show-icons".to_string());
+        }
+
+        let mut hints = bind_keys(&mut args, menu.keys);
+        if multi {
+            hints.push("Shift+Enter: Mark".to_string());
+        }
+
+        let mut mesg: Vec<String> = menu.mesg.iter().map(|m| m.to_string()).collect();
+        if !hints.is_empty() {
+            mesg.push(hints.join("  "));
+        }
+        if !mesg.is_empty() {
+            args.push("-mesg".to_string());
+            args.push(mesg.join("\n"));
+        }
+
+        let mut child = Command::new("rofi")
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .context("Failed to spawn rofi")?;
+
+        {
+            let stdin = child.stdin.as_mut().context("Failed to get stdin")?;
+            for (i, option) in menu.options.iter().enumerate() {
+                match menu.icons.get(i) {
+                    Some(icon) => writeln!(stdin, "{}\0icon\x1f{}", option, icon)?,
+                    None => writeln!(stdin, "{}", option)?,
+                }
+            }
+        }
+
+        let output = child
+            .wait_with_output()
+            .context("Failed to wait for rofi")?;
+
+        let Some(custom_key) = custom_key(&output.status, menu.keys.len()) else {
+            return Ok(None);
+        };
+
+        // One index per line with multi-select
+        let indices = String::from_utf8(output.stdout)
+            .context("Invalid UTF-8 from rofi")?
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(|l| l.parse::<usize>().context("Invalid index from rofi"))
+            .collect::<Result<Vec<_>>>()?;
+
+        if indices.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(Selection {
+                indices,
+                custom_key,
+            }))
+        }
+    }
+}
+
+impl Launcher for Rofi {
+    fn input(&self, prompt: &Prompt) -> Result<Option<String>> {
+        Ok(self.input_with_keys(prompt, &[])?.map(|(text, _)| text))
+    }
+
+    fn input_with_keys(
+        &self,
+        prompt: &Prompt,
+        keys: &[(&str, &str)],
+    ) -> Result<Option<(String, Option<usize>)>> {
+        let theme_str = format!(
+            "entry {{ placeholder: {}; }}",
+            markup::rasi_string(prompt.placeholder)
+        );
+
+        // Suggestions are listed below the entry, otherwise the list is hidden
+        let lines = prompt.suggestions.len().min(MAX_SUGGESTION_ROWS);
+        let mut args: Vec<String> = ["-dmenu", "-p", prompt.prompt, "-l", &lines.to_string()]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        args.extend(self.theme.args(&prompt.style_id(), Some(&theme_str)));
+        if let Some(initial) = prompt.initial {
+            args.extend(["-filter".to_string(), initial.to_string()]);
+        }
+
+        let mut hints = bind_keys(&mut args, keys);
+        if !prompt.suggestions.is_empty() {
+            // rofi's own bindings: a row matching the typed text would
+            // otherwise win on Enter
+            hints.push("Ctrl+Space: Edit row".to_string());
+            hints.push("Ctrl+Enter: Use typed text".to_string());
+        }
+
+        let mut mesg: Vec<String> = prompt.mesg.iter().map(|m| m.to_string()).collect();
+        if !hints.is_empty() {
+            mesg.push(hints.join("  "));
+        }
+        if !mesg.is_empty() {
+            args.push("-mesg".to_string());
+            args.push(mesg.join("\n"));
+        }
+
+        let mut child = Command::new("rofi")
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::inherit())
+            .spawn()
+            .context("Failed to spawn rofi")?;
+
+        {
+            let stdin = child.stdin.as_mut().context("Failed to get stdin")?;
+            for suggestion in prompt.suggestions {
+                writeln!(stdin, "{}", suggestion)?;
+            }
+        }
+
+        let output = child
+            .wait_with_output()
+            .context("Failed to wait for rofi")?;
+
+        // rofi exits with 1 on Escape but 0 on an empty Enter
+        let Some(custom_key) = custom_key(&output.status, keys.len()) else {
+            return Ok(None);
+        };
+
+        let text = String::from_utf8(output.stdout)
+            .context("Invalid UTF-8 from rofi")?
+            .trim()
+            .to_string();
+
+        Ok(Some((text, custom_key)))
+    }
+
+    fn select(&self, menu: &Menu) -> Result<Option<Selection>> {
+        self.dmenu_select(menu, false)
+    }
+
+    fn multi_select(&self, menu: &Menu) -> Result<Option<Selection>> {
+        self.dmenu_select(menu, true)
+    }
+
+    fn error(&self, message: &str) -> Result<()> {
+        Command::new("rofi")
+            .args(["-markup", "-e", &markup::escape(message)])
+            .args(self.theme.args("error", None))
+            .status()
+            .context("Failed to show error in rofi")?;
+        Ok(())
+    }
+
+    fn supports_keys(&self) -> bool {
+        true
+    }
+
+    fn supports_message(&self) -> bool {
+        true
+    }
+
+    fn supports_markup(&self) -> bool {
+        true
+    }
+}
+
+/// Bind `(binding, label)` keys to kb-custom-1.. and return their hints
+fn bind_keys(args: &mut Vec<String>, keys: &[(&str, &str)]) -> Vec<String> {
+    let mut hints = Vec::new();
+    for (i, (binding, label)) in keys.iter().enumerate() {
+        args.push(format!("-kb-custom-{}", i + 1));
+        args.push(binding.to_string());
+        hints.push(format!(
+            "{}: {}",
+            markup::escape(binding),
+            markup::escape(label)
+        ));
+    }
+    hints
+}
+
+/// The custom key rofi exited with, `Some(None)` for Enter and `None` for a cancel
+///
+/// rofi exits with 10 + n for kb-custom-(n + 1).
+fn custom_key(status: &ExitStatus, keys: usize) -> Option<Option<usize>> {
+    match status.code() {
+        Some(0) => Some(None),
+        Some(code) if (10..10 + keys as i32).contains(&code) => Some(Some((code - 10) as usize)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bind_keys_escapes_hints() {
+        let mut args = Vec::new();
+        let hints = bind_keys(
+            &mut args,
+            &[("Alt+1", "Copy \"link\" & <id>"), ("Alt+<", r"Back\up 'n'")],
+        );
+
+        // The bindings themselves go to rofi untouched
+        assert_eq!(
+            args,
+            ["-kb-custom-1", "Alt+1", "-kb-custom-2", "Alt+<"].map(String::from)
+        );
+        assert_eq!(
+            hints,
+            [
+                "Alt+1: Copy &quot;link&quot; &amp; &lt;id&gt;",
+                r"Alt+&lt;: Back\up &apos;n&apos;",
+            ]
+        );
+    }
+}
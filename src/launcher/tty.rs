@@ -0,0 +1,256 @@
+use anyhow::{Context, Result};
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::sync::Mutex;
+
+use super::{Launcher, Menu, Prompt, Selection};
+use crate::markup;
+
+/// Plain prompts in the terminal, for when there's no display to open a
+/// launcher on
+///
+/// Talks to `/dev/tty` so piped stdin and stdout are left alone. Lines are
+/// edited with the terminal's own keys (Backspace, Ctrl+U, Ctrl+W) and
+/// Ctrl+D cancels.
+#[derive(Default)]
+pub struct Tty {
+    /// Opened on the first prompt and kept, so lines read ahead from a
+    /// multi-line paste are there for the next prompt
+    terminal: Mutex<Option<BufReader<File>>>,
+}
+
+impl Tty {
+    /// Print `text`, then read a line after `prompt`; `None` on Ctrl+D
+    fn ask(&self, text: &str, prompt: &str) -> Result<Option<String>> {
+        let mut terminal = self.terminal.lock().unwrap_or_else(|e| e.into_inner());
+        let reader = match terminal.as_mut() {
+            Some(reader) => reader,
+            None => terminal.insert(BufReader::new(
+                OpenOptions::new()
+                    .read(true)
+                    .write(true)
+                    .open("/dev/tty")
+                    .context("No terminal to prompt on")?,
+            )),
+        };
+
+        let mut out: &File = reader.get_ref();
+        write!(out, "{}{}", text, prompt)?;
+        out.flush()?;
+
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 {
+            writeln!(reader.get_ref())?;
+            return Ok(None);
+        }
+
+        Ok(Some(line.trim_end_matches(['\n', '\r']).to_string()))
+    }
+}
+
+/// Whether the letters of `query` appear in order in `text`, ignoring case
+fn fuzzy_match(query: &str, text: &str) -> bool {
+    let mut chars = text.chars().flat_map(char::to_lowercase);
+    query
+        .chars()
+        .flat_map(char::to_lowercase)
+        .filter(|c| !c.is_whitespace())
+        .all(|q| chars.any(|c| c == q))
+}
+
+/// Row numbers typed as `3` or `1 4 5`, if there's at least one and every
+/// one is in `1..=rows`; a number typed twice counts once
+fn parse_numbers(line: &str, rows: usize) -> Option<Vec<usize>> {
+    let mut numbers: Vec<usize> = Vec::new();
+    for n in line
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|n| !n.is_empty())
+    {
+        let n = n.parse::<usize>().ok().filter(|n| (1..=rows).contains(n))?;
+        if !numbers.contains(&n) {
+            numbers.push(n);
+        }
+    }
+    (!numbers.is_empty()).then_some(numbers)
+}
+
+impl Tty {
+    fn pick(&self, menu: &Menu, multi: bool) -> Result<Option<Selection>> {
+        let all: Vec<usize> = (0..menu.options.len()).collect();
+        let mut rows = all.clone();
+        let mut note = "";
+
+        loop {
+            let mut text = String::from("\n");
+            if let Some(mesg) = menu.mesg {
+                text.push_str(&markup::strip(mesg));
+                text.push('\n');
+            }
+
+            let width = rows.len().to_string().len();
+            for (n, &i) in rows.iter().enumerate() {
+                let marker = if menu.selected == Some(i) { '>' } else { ' ' };
+                text.push_str(&format!(
+                    "{} {:>width$}) {}\n",
+                    marker,
+                    n + 1,
+                    menu.options[i]
+                ));
+            }
+
+            text.push_str(note);
+            text.push_str(if multi {
+                "Numbers separated by spaces, or text to filter"
+            } else {
+                "A number, or text to filter"
+            });
+            if rows.len() == 1 {
+                text.push_str("; Enter picks it");
+            } else if menu.selected.is_some_and(|s| rows.contains(&s)) {
+                text.push_str("; Enter picks the > row");
+            }
+            text.push('\n');
+
+            let Some(line) = self.ask(&text, &format!("{}> ", menu.prompt))? else {
+                return Ok(None);
+            };
+            let line = line.trim();
+
+            let indices = if line.is_empty() {
+                let only = (rows.len() == 1).then(|| rows[0]);
+                match only.or(menu.selected.filter(|s| rows.contains(s))) {
+                    Some(row) => vec![row],
+                    None => return Ok(None),
+                }
+            } else if let Some(numbers) = parse_numbers(line, rows.len()) {
+                if !multi && numbers.len() > 1 {
+                    note = "Pick a single row. ";
+                    continue;
+                }
+                numbers.iter().map(|&n| rows[n - 1]).collect()
+            } else {
+                // Filter the whole list again, so a typo doesn't narrow it for good
+                let matches: Vec<usize> = all
+                    .iter()
+                    .copied()
+                    .filter(|&i| fuzzy_match(line, &menu.options[i]))
+                    .collect();
+                // Even a single match waits for Enter, so a few letters
+                // can't act on a row unseen
+                if matches.is_empty() {
+                    note = "No matches. ";
+                } else {
+                    note = "";
+                    rows = matches;
+                }
+                continue;
+            };
+
+            return Ok(Some(Selection {
+                indices,
+                custom_key: None,
+            }));
+        }
+    }
+}
+
+impl Launcher for Tty {
+    fn input(&self, prompt: &Prompt) -> Result<Option<String>> {
+        let mut text = String::from("\n");
+        if let Some(mesg) = prompt.mesg {
+            text.push_str(&markup::strip(mesg));
+            text.push('\n');
+        }
+        for (n, suggestion) in prompt.suggestions.iter().enumerate() {
+            text.push_str(&format!("  #{} {}\n", n + 1, suggestion));
+        }
+        if !prompt.suggestions.is_empty() {
+            text.push_str("Type #N to pick one of these\n");
+        }
+
+        // Enter on an empty line keeps the initial text
+        let label = match prompt.initial {
+            Some(initial) => format!("{} [{}]: ", prompt.prompt, initial),
+            None => format!("{} ({}): ", prompt.prompt, prompt.placeholder),
+        };
+
+        let Some(line) = self.ask(&text, &label)? else {
+            return Ok(None);
+        };
+        let line = line.trim();
+
+        let picked = line
+            .strip_prefix('#')
+            .and_then(|n| n.parse::<usize>().ok())
+            .and_then(|n| prompt.suggestions.get(n.wrapping_sub(1)));
+
+        Ok(Some(match (picked, prompt.initial) {
+            (Some(suggestion), _) => suggestion.clone(),
+            (None, Some(initial)) if line.is_empty() => initial.to_string(),
+            _ => line.to_string(),
+        }))
+    }
+
+    fn select(&self, menu: &Menu) -> Result<Option<Selection>> {
+        self.pick(menu, false)
+    }
+
+    fn multi_select(&self, menu: &Menu) -> Result<Option<Selection>> {
+        self.pick(menu, true)
+    }
+
+    fn error(&self, message: &str) -> Result<()> {
+        super::print_error(message);
+        Ok(())
+    }
+
+    fn supports_message(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_matches_letters_in_order() {
+        assert!(fuzzy_match("eng12", "ENG-123 Fix login"));
+        assert!(fuzzy_match("fix login", "ENG-123 Fix login"));
+        assert!(fuzzy_match("FXLGN", "fix login"));
+        assert!(fuzzy_match("", "anything"));
+        assert!(!fuzzy_match("login fix", "Fix login"));
+        assert!(!fuzzy_match("xyz", "Fix login"));
+        assert!(!fuzzy_match("loginn", "Fix login"));
+    }
+
+    #[test]
+    fn parses_numbers_in_range() {
+        assert_eq!(parse_numbers("3", 5), Some(vec![3]));
+        assert_eq!(parse_numbers("1 4 5", 5), Some(vec![1, 4, 5]));
+        assert_eq!(parse_numbers(" 2,3 ,  1 ", 3), Some(vec![2, 3, 1]));
+        assert_eq!(parse_numbers("1", 1), Some(vec![1]));
+    }
+
+    #[test]
+    fn rejects_numbers_out_of_range_or_invalid() {
+        assert_eq!(parse_numbers("0", 5), None);
+        assert_eq!(parse_numbers("6", 5), None);
+        assert_eq!(parse_numbers("1 6", 5), None);
+        assert_eq!(parse_numbers("1", 0), None);
+        assert_eq!(parse_numbers("-1", 5), None);
+        assert_eq!(parse_numbers("2a", 5), None);
+        assert_eq!(parse_numbers("1.5", 5), None);
+        assert_eq!(parse_numbers("99999999999999999999999", 5), None);
+        // Text is a filter, not numbers
+        assert_eq!(parse_numbers("fix 2", 5), None);
+    }
+
+    #[test]
+    fn empty_and_duplicate_numbers() {
+        assert_eq!(parse_numbers("", 5), None);
+        assert_eq!(parse_numbers(" , ,", 5), None);
+        assert_eq!(parse_numbers("2 2", 5), Some(vec![2]));
+        assert_eq!(parse_numbers("3 1 3 1", 5), Some(vec![3, 1]));
+    }
+}
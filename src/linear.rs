@@ -1,9 +1,13 @@
 use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
 use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use serde_json::json;
+use std::collections::HashMap;
 
 const ENDPOINT: &str = "https://api.linear.app/graphql";
+/// Mutations per batched request, well within Linear's query complexity limit
+const BATCH_SIZE: usize = 50;
 
 #[derive(Debug, Deserialize)]
 pub struct GraphQLResponse<T> {
@@ -14,6 +18,86 @@ pub struct GraphQLResponse<T> {
 #[derive(Debug, Deserialize)]
 pub struct GraphQLError {
     pub message: String,
+    /// Response path the error applies to, e.g. a mutation alias
+    #[serde(default)]
+    pub path: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub extensions: ErrorExtensions,
+}
+
+/// Machine-readable details Linear attaches to an error
+#[derive(Debug, Deserialize, Default)]
+pub struct ErrorExtensions {
+    /// e.g. `ENTITY_NOT_FOUND`
+    pub code: Option<String>,
+    /// e.g. `entity not found`
+    #[serde(rename = "type")]
+    pub kind: Option<String>,
+}
+
+impl GraphQLError {
+    /// Whether the error says a requested entity doesn't exist, as Linear
+    /// answers for an unknown issue id
+    ///
+    /// Goes by the error's code or type, and by the message only when the
+    /// error carries neither.
+    pub fn is_not_found(&self) -> bool {
+        let ErrorExtensions { code, kind } = &self.extensions;
+        if code.is_none() && kind.is_none() {
+            return self.message.to_lowercase().contains("not found");
+        }
+        code.as_deref()
+            .is_some_and(|c| c.eq_ignore_ascii_case("ENTITY_NOT_FOUND"))
+            || kind
+                .as_deref()
+                .is_some_and(|k| k.eq_ignore_ascii_case("entity not found"))
+    }
+}
+
+/// Per-alias outcome of a batched mutation
+///
+/// Errors point back at their alias `m<i>` through `path`. Errors without
+/// one (a rejected request, say) apply to every alias that didn't succeed,
+/// so a null alias always reports why instead of passing or failing blankly.
+fn batch_results(
+    field: &str,
+    count: usize,
+    response: GraphQLResponse<HashMap<String, Option<MutationResult>>>,
+) -> Vec<Result<(), String>> {
+    let errors = response.errors.unwrap_or_default();
+    let data = response.data.unwrap_or_default();
+
+    let alias_of = |e: &GraphQLError| {
+        e.path
+            .first()
+            .and_then(|p| p.as_str())
+            .and_then(|p| p.strip_prefix('m'))
+            .and_then(|i| i.parse::<usize>().ok())
+            .filter(|&i| i < count)
+    };
+    let general: Vec<&str> = errors
+        .iter()
+        .filter(|e| alias_of(e).is_none())
+        .map(|e| e.message.as_str())
+        .collect();
+
+    (0..count)
+        .map(|i| {
+            let own: Vec<&str> = errors
+                .iter()
+                .filter(|e| alias_of(e) == Some(i))
+                .map(|e| e.message.as_str())
+                .collect();
+            if !own.is_empty() {
+                return Err(own.join("; "));
+            }
+            match data.get(&format!("m{}", i)) {
+                Some(Some(r)) if r.success => Ok(()),
+                _ if !general.is_empty() => Err(general.join("; ")),
+                _ => Err(format!("{} failed", field)),
+            }
+        })
+        .collect()
 }
 
 #[derive(Debug, Deserialize)]
@@ -21,9 +105,8 @@ pub struct ViewerData {
     pub viewer: Viewer,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Serialize, Deserialize)]
 pub struct Viewer {
-    #[allow(dead_code)]
     pub id: String,
     pub name: String,
     pub email: String,
@@ -58,15 +141,458 @@ pub struct IssueCreateResult {
     pub issue: Option<Issue>,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct IssueUpdateData {
+    #[serde(rename = "issueUpdate")]
+    pub issue_update: IssueCreateResult,
+}
+
 #[derive(Debug, Deserialize)]
 pub struct Issue {
-    #[allow(dead_code)]
     pub id: String,
     pub identifier: String,
     pub url: String,
     pub title: String,
 }
 
+#[derive(Debug, Deserialize)]
+pub struct TeamStatesData {
+    pub team: TeamStates,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamStates {
+    pub states: StatesNodes,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatesNodes {
+    pub nodes: Vec<WorkflowState>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct WorkflowState {
+    pub id: String,
+    pub name: String,
+    /// triage, backlog, unstarted, started, completed or canceled
+    #[serde(rename = "type")]
+    pub state_type: String,
+    pub position: f64,
+    /// Hex color, e.g. `#f2c94c`
+    pub color: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamLabelsData {
+    pub team: TeamLabels,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamLabels {
+    pub labels: LabelsNodes,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LabelsNodes {
+    pub nodes: Vec<IssueLabel>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct IssueLabel {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignedIssuesData {
+    pub viewer: AssignedIssuesViewer,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignedIssuesViewer {
+    #[serde(rename = "assignedIssues")]
+    pub assigned_issues: IssueConnection,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssueConnection {
+    pub nodes: Vec<IssueSummary>,
+    #[serde(rename = "pageInfo")]
+    pub page_info: PageInfo,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PageInfo {
+    #[serde(rename = "hasNextPage")]
+    pub has_next_page: bool,
+    #[serde(rename = "endCursor")]
+    pub end_cursor: Option<String>,
+}
+
+/// GraphQL fragment selecting the fields of `IssueSummary`
+macro_rules! issue_summary_fragment {
+    () => {
+        r#"
+            fragment IssueSummaryFields on Issue {
+                id
+                identifier
+                title
+                url
+                priority
+                updatedAt
+                state {
+                    id
+                    name
+                    color
+                }
+                team {
+                    id
+                    key
+                }
+                assignee {
+                    id
+                    name
+                }
+            }
+        "#
+    };
+}
+
+/// An issue as shown in rofi lists
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IssueSummary {
+    pub id: String,
+    pub identifier: String,
+    pub title: String,
+    pub url: String,
+    pub priority: u8,
+    #[serde(rename = "updatedAt")]
+    pub updated_at: String,
+    pub state: IssueState,
+    pub team: IssueTeam,
+    pub assignee: Option<User>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IssueState {
+    pub id: String,
+    pub name: String,
+    /// Hex color; empty in results cached before it was fetched
+    #[serde(default)]
+    pub color: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct IssueTeam {
+    pub id: String,
+    pub key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct User {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamMembersData {
+    pub team: TeamMembers,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamMembers {
+    pub members: MembersNodes,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MembersNodes {
+    pub nodes: Vec<User>,
+}
+
+/// Everything shown in the issue detail view
+#[derive(Debug, Deserialize, Clone)]
+pub struct IssueDetails {
+    #[serde(flatten)]
+    pub summary: IssueSummary,
+    pub description: Option<String>,
+    pub labels: LabelsNodes,
+    pub project: Option<Project>,
+    pub cycle: Option<Cycle>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Project {
+    pub name: String,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct Cycle {
+    pub number: u32,
+    pub name: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssueDetailsData {
+    pub issue: IssueDetails,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssueData {
+    pub issue: IssueSummary,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchIssuesData {
+    #[serde(rename = "searchIssues")]
+    pub search_issues: SearchIssuesNodes,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SearchIssuesNodes {
+    pub nodes: Vec<IssueSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentCreateData {
+    #[serde(rename = "commentCreate")]
+    pub comment_create: CommentCreateResult,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CommentCreateResult {
+    pub success: bool,
+    pub comment: Option<Comment>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Comment {
+    pub url: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MutationResult {
+    pub success: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationsData {
+    pub notifications: NotificationConnection,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct NotificationConnection {
+    pub nodes: Vec<Notification>,
+    #[serde(rename = "pageInfo")]
+    pub page_info: PageInfo,
+}
+
+/// An inbox notification; only issue notifications carry an issue
+#[derive(Debug, Deserialize, Clone)]
+pub struct Notification {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub notification_type: String,
+    #[serde(rename = "readAt")]
+    pub read_at: Option<String>,
+    pub actor: Option<User>,
+    pub issue: Option<IssueSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CustomViewsData {
+    #[serde(rename = "customViews")]
+    pub custom_views: CustomViewsNodes,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CustomViewsNodes {
+    pub nodes: Vec<CustomView>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CustomView {
+    pub name: String,
+    pub description: Option<String>,
+    /// The view's issue filter, in the shape of `IssueFilter`
+    #[serde(rename = "filterData")]
+    pub filter_data: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssuesData {
+    pub issues: IssueConnection,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActiveCycleData {
+    pub team: ActiveCycleTeam,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActiveCycleTeam {
+    #[serde(rename = "activeCycle")]
+    pub active_cycle: Option<ActiveCycle>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ActiveCycle {
+    pub number: u32,
+    pub name: Option<String>,
+    #[serde(rename = "endsAt")]
+    pub ends_at: DateTime<Utc>,
+    /// Completion ratio between 0 and 1
+    pub progress: f64,
+    pub issues: IssuesNodes,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssuesNodes {
+    pub nodes: Vec<IssueSummary>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct IssueRelationCreateData {
+    #[serde(rename = "issueRelationCreate")]
+    pub issue_relation_create: MutationResult,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamCyclesData {
+    pub team: TeamCycles,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamCycles {
+    pub cycles: CyclesNodes,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct CyclesNodes {
+    pub nodes: Vec<CycleOption>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct CycleOption {
+    pub id: String,
+    pub number: u32,
+    pub name: Option<String>,
+}
+
+/// Fields to change with `issueUpdate`; unset fields are left untouched
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct IssueUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub team_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub state_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label_ids: Option<Vec<String>>,
+    /// Labels to add without touching the existing ones
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub added_label_ids: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cycle_id: Option<String>,
+    /// `Some(None)` unassigns the issue
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee_id: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub snoozed_until_at: Option<DateTime<Utc>>,
+}
+
+impl IssueUpdate {
+    pub fn is_empty(&self) -> bool {
+        self.team_id.is_none()
+            && self.title.is_none()
+            && self.description.is_none()
+            && self.state_id.is_none()
+            && self.priority.is_none()
+            && self.label_ids.is_none()
+            && self.added_label_ids.is_none()
+            && self.cycle_id.is_none()
+            && self.assignee_id.is_none()
+            && self.snoozed_until_at.is_none()
+    }
+}
+
+/// Linear priority values and their display names
+pub const PRIORITIES: [(u8, &str); 5] = [
+    (0, "No priority"),
+    (1, "Urgent"),
+    (2, "High"),
+    (3, "Medium"),
+    (4, "Low"),
+];
+
+pub fn priority_name(priority: u8) -> &'static str {
+    PRIORITIES
+        .iter()
+        .find(|(p, _)| *p == priority)
+        .map(|(_, name)| *name)
+        .unwrap_or("No priority")
+}
+
+/// Short glyph for a priority, for rows in text-only launchers too
+pub fn priority_glyph(priority: u8) -> &'static str {
+    match priority {
+        1 => "⚠",
+        2 => "▂▄▆",
+        3 => "▂▄",
+        4 => "▂",
+        _ => "–",
+    }
+}
+
+/// Sort key putting urgent issues first and unprioritized issues last
+pub fn priority_rank(priority: u8) -> u8 {
+    if priority == 0 {
+        u8::MAX
+    } else {
+        priority
+    }
+}
+
+/// Extract an issue identifier from `ENG-123` or a Linear issue URL
+///
+/// Identifiers are normalized to upper case. Returns `None` for anything
+/// else, so free text can be told apart from a reference to an issue.
+pub fn parse_issue_ref(input: &str) -> Option<String> {
+    let input = input.trim();
+
+    // https://linear.app/<workspace>/issue/ENG-123/optional-slug
+    let candidate = match input.split_once("linear.app/") {
+        Some((scheme, path))
+            if scheme.is_empty()
+                || scheme.ends_with("//")
+                || scheme == "www."
+                || scheme.ends_with("//www.") =>
+        {
+            let mut segments = path.split(['/', '?', '#']);
+            segments.find(|s| *s == "issue")?;
+            segments.next()?
+        }
+        Some(_) => return None,
+        None => input,
+    };
+
+    let (key, number) = candidate.split_once('-')?;
+    let valid_key = key.chars().next().is_some_and(|c| c.is_ascii_alphabetic())
+        && key.chars().all(|c| c.is_ascii_alphanumeric());
+    let valid_number = !number.is_empty() && number.chars().all(|c| c.is_ascii_digit());
+
+    if valid_key && valid_number {
+        Some(format!("{}-{}", key.to_ascii_uppercase(), number))
+    } else {
+        None
+    }
+}
+
 pub struct LinearClient {
     client: Client,
     api_key: String,
@@ -80,11 +606,11 @@ impl LinearClient {
         }
     }
 
-    async fn query<T: for<'de> Deserialize<'de>>(
+    async fn send<T: for<'de> Deserialize<'de>>(
         &self,
         query: &str,
         variables: Option<serde_json::Value>,
-    ) -> Result<T> {
+    ) -> Result<GraphQLResponse<T>> {
         let body = json!({
             "query": query,
             "variables": variables.unwrap_or(json!({}))
@@ -106,10 +632,18 @@ impl LinearClient {
             anyhow::bail!("Linear API error ({}): {}", status, text);
         }
 
-        let result: GraphQLResponse<T> = response
+        response
             .json()
             .await
-            .context("Failed to parse Linear API response")?;
+            .context("Failed to parse Linear API response")
+    }
+
+    async fn query<T: for<'de> Deserialize<'de>>(
+        &self,
+        query: &str,
+        variables: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let result: GraphQLResponse<T> = self.send(query, variables).await?;
 
         if let Some(errors) = result.errors {
             let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
@@ -119,6 +653,64 @@ impl LinearClient {
         result.data.context("No data in response")
     }
 
+    /// Run the same mutation once per item, using aliases to send up to
+    /// `BATCH_SIZE` items per request
+    ///
+    /// `args` names the mutation's arguments and their GraphQL types; each item
+    /// holds one value per argument. Returns one result per item, so a failure
+    /// on one item doesn't hide the others.
+    async fn batch_mutation(
+        &self,
+        field: &str,
+        args: &[(&str, &str)],
+        items: &[Vec<serde_json::Value>],
+    ) -> Result<Vec<Result<(), String>>> {
+        let mut results = Vec::with_capacity(items.len());
+        for chunk in items.chunks(BATCH_SIZE) {
+            results.extend(self.batch_request(field, args, chunk).await?);
+        }
+        Ok(results)
+    }
+
+    /// One request of `batch_mutation`, with the items aliased `m0`, `m1`, ...
+    async fn batch_request(
+        &self,
+        field: &str,
+        args: &[(&str, &str)],
+        items: &[Vec<serde_json::Value>],
+    ) -> Result<Vec<Result<(), String>>> {
+        let mut params = Vec::new();
+        let mut fields = Vec::new();
+        let mut variables = serde_json::Map::new();
+
+        for (i, values) in items.iter().enumerate() {
+            let mut call_args = Vec::new();
+            for ((name, ty), value) in args.iter().zip(values) {
+                params.push(format!("${}{}: {}", name, i, ty));
+                call_args.push(format!("{}: ${}{}", name, name, i));
+                variables.insert(format!("{}{}", name, i), value.clone());
+            }
+            fields.push(format!(
+                "m{}: {}({}) {{ success }}",
+                i,
+                field,
+                call_args.join(", ")
+            ));
+        }
+
+        let mutation = format!(
+            "mutation Batch({}) {{ {} }}",
+            params.join(", "),
+            fields.join(" ")
+        );
+
+        let result: GraphQLResponse<HashMap<String, Option<MutationResult>>> = self
+            .send(&mutation, Some(serde_json::Value::Object(variables)))
+            .await?;
+
+        Ok(batch_results(field, items.len(), result))
+    }
+
     pub async fn get_viewer(&self) -> Result<Viewer> {
         const QUERY: &str = r#"
             query {
@@ -185,4 +777,644 @@ impl LinearClient {
 
         data.issue_create.issue.context("No issue returned")
     }
+
+    pub async fn update_issue(&self, id: &str, input: &IssueUpdate) -> Result<Issue> {
+        const MUTATION: &str = r#"
+            mutation UpdateIssue($id: String!, $input: IssueUpdateInput!) {
+                issueUpdate(id: $id, input: $input) {
+                    success
+                    issue {
+                        id
+                        identifier
+                        url
+                        title
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "id": id,
+            "input": input
+        });
+
+        let data: IssueUpdateData = self.query(MUTATION, Some(variables)).await?;
+
+        if !data.issue_update.success {
+            anyhow::bail!("Failed to update issue");
+        }
+
+        data.issue_update.issue.context("No issue returned")
+    }
+
+    pub async fn get_workflow_states(&self, team_id: &str) -> Result<Vec<WorkflowState>> {
+        const QUERY: &str = r#"
+            query TeamStates($teamId: String!) {
+                team(id: $teamId) {
+                    states {
+                        nodes {
+                            id
+                            name
+                            type
+                            position
+                            color
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({ "teamId": team_id });
+        let data: TeamStatesData = self.query(QUERY, Some(variables)).await?;
+
+        let mut states = data.team.states.nodes;
+        states.sort_by(|a, b| a.position.total_cmp(&b.position));
+        Ok(states)
+    }
+
+    pub async fn get_labels(&self, team_id: &str) -> Result<Vec<IssueLabel>> {
+        const QUERY: &str = r#"
+            query TeamLabels($teamId: String!) {
+                team(id: $teamId) {
+                    labels {
+                        nodes {
+                            id
+                            name
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({ "teamId": team_id });
+        let data: TeamLabelsData = self.query(QUERY, Some(variables)).await?;
+        Ok(data.team.labels.nodes)
+    }
+
+    /// Fetch every open issue assigned to the viewer, following pagination
+    pub async fn get_assigned_issues(&self) -> Result<Vec<IssueSummary>> {
+        const QUERY: &str = concat!(
+            r#"
+            query AssignedIssues($after: String) {
+                viewer {
+                    assignedIssues(
+                        first: 100
+                        after: $after
+                        filter: { state: { type: { nin: ["completed", "canceled"] } } }
+                    ) {
+                        nodes {
+                            ...IssueSummaryFields
+                        }
+                        pageInfo {
+                            hasNextPage
+                            endCursor
+                        }
+                    }
+                }
+            }
+        "#,
+            issue_summary_fragment!()
+        );
+
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let variables = json!({ "after": after });
+            let data: AssignedIssuesData = self.query(QUERY, Some(variables)).await?;
+            let connection = data.viewer.assigned_issues;
+
+            issues.extend(connection.nodes);
+
+            match connection.page_info.end_cursor {
+                Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+
+        Ok(issues)
+    }
+
+    /// Full-text search across the workspace's issues
+    pub async fn search_issues(&self, term: &str) -> Result<Vec<IssueSummary>> {
+        const QUERY: &str = concat!(
+            r#"
+            query SearchIssues($term: String!) {
+                searchIssues(term: $term, first: 50) {
+                    nodes {
+                        ...IssueSummaryFields
+                    }
+                }
+            }
+        "#,
+            issue_summary_fragment!()
+        );
+
+        let variables = json!({ "term": term });
+        let data: SearchIssuesData = self.query(QUERY, Some(variables)).await?;
+        Ok(data.search_issues.nodes)
+    }
+
+    /// Fetch a single issue by id or identifier such as `ENG-123`
+    pub async fn get_issue(&self, id: &str) -> Result<IssueSummary> {
+        const QUERY: &str = concat!(
+            r#"
+            query Issue($id: String!) {
+                issue(id: $id) {
+                    ...IssueSummaryFields
+                }
+            }
+        "#,
+            issue_summary_fragment!()
+        );
+
+        let variables = json!({ "id": id });
+        let data: IssueData = self.query(QUERY, Some(variables)).await?;
+        Ok(data.issue)
+    }
+
+    pub async fn create_comment(&self, issue_id: &str, body: &str) -> Result<Comment> {
+        const MUTATION: &str = r#"
+            mutation CreateComment($issueId: String!, $body: String!) {
+                commentCreate(input: { issueId: $issueId, body: $body }) {
+                    success
+                    comment {
+                        url
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "issueId": issue_id,
+            "body": body
+        });
+
+        let data: CommentCreateData = self.query(MUTATION, Some(variables)).await?;
+
+        if !data.comment_create.success {
+            anyhow::bail!("Failed to create comment");
+        }
+
+        data.comment_create.comment.context("No comment returned")
+    }
+
+    pub async fn get_team_members(&self, team_id: &str) -> Result<Vec<User>> {
+        const QUERY: &str = r#"
+            query TeamMembers($teamId: String!) {
+                team(id: $teamId) {
+                    members(first: 250) {
+                        nodes {
+                            id
+                            name
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({ "teamId": team_id });
+        let data: TeamMembersData = self.query(QUERY, Some(variables)).await?;
+        Ok(data.team.members.nodes)
+    }
+
+    pub async fn get_issue_details(&self, id: &str) -> Result<IssueDetails> {
+        self.find_issue_details(id)
+            .await?
+            .with_context(|| format!("Issue {} not found", id))
+    }
+
+    /// Fetch an issue's details, or `None` if no issue has that id or identifier
+    ///
+    /// Any other failure, such as a network or auth error, is still an error.
+    pub async fn find_issue_details(&self, id: &str) -> Result<Option<IssueDetails>> {
+        const QUERY: &str = concat!(
+            r#"
+            query IssueDetails($id: String!) {
+                issue(id: $id) {
+                    ...IssueSummaryFields
+                    description
+                    labels {
+                        nodes {
+                            id
+                            name
+                        }
+                    }
+                    project {
+                        name
+                    }
+                    cycle {
+                        number
+                        name
+                    }
+                }
+            }
+        "#,
+            issue_summary_fragment!()
+        );
+
+        let variables = json!({ "id": id });
+        let result: GraphQLResponse<IssueDetailsData> = self.send(QUERY, Some(variables)).await?;
+
+        match result.errors {
+            Some(errors) if errors.iter().all(GraphQLError::is_not_found) => Ok(None),
+            Some(errors) => {
+                let messages: Vec<_> = errors.iter().map(|e| e.message.as_str()).collect();
+                anyhow::bail!("GraphQL errors: {}", messages.join(", "));
+            }
+            None => Ok(Some(result.data.context("No data in response")?.issue)),
+        }
+    }
+
+    /// Every unread inbox notification, of any type, following pagination
+    ///
+    /// Read ones are dropped as the pages come in, since unread notifications
+    /// can sit behind any number of read ones.
+    pub async fn get_unread_notifications(&self) -> Result<Vec<Notification>> {
+        const QUERY: &str = concat!(
+            r#"
+            query Notifications($after: String) {
+                notifications(first: 100, after: $after) {
+                    nodes {
+                        id
+                        type
+                        readAt
+                        actor {
+                            id
+                            name
+                        }
+                        ... on IssueNotification {
+                            issue {
+                                ...IssueSummaryFields
+                            }
+                        }
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }
+        "#,
+            issue_summary_fragment!()
+        );
+
+        let mut unread = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let variables = json!({ "after": after });
+            let data: NotificationsData = self.query(QUERY, Some(variables)).await?;
+            let connection = data.notifications;
+
+            unread.extend(connection.nodes.into_iter().filter(|n| n.read_at.is_none()));
+
+            match connection.page_info.end_cursor {
+                Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+
+        Ok(unread)
+    }
+
+    /// Mark notifications as read, batched
+    pub async fn mark_notifications_read(&self, ids: &[String]) -> Result<()> {
+        let input = json!({ "readAt": Utc::now().to_rfc3339() });
+        let items: Vec<Vec<serde_json::Value>> = ids
+            .iter()
+            .map(|id| vec![json!(id), input.clone()])
+            .collect();
+
+        let results = self
+            .batch_mutation(
+                "notificationUpdate",
+                &[("id", "String!"), ("input", "NotificationUpdateInput!")],
+                &items,
+            )
+            .await?;
+
+        if let Some(Err(message)) = results.into_iter().find(|r| r.is_err()) {
+            anyhow::bail!("Failed to mark notifications as read: {}", message);
+        }
+
+        Ok(())
+    }
+
+    /// Apply one update per issue, batched
+    pub async fn update_issues(
+        &self,
+        updates: &[(String, IssueUpdate)],
+    ) -> Result<Vec<Result<(), String>>> {
+        let items = updates
+            .iter()
+            .map(|(id, update)| Ok(vec![json!(id), serde_json::to_value(update)?]))
+            .collect::<Result<Vec<_>>>()?;
+
+        self.batch_mutation(
+            "issueUpdate",
+            &[("id", "String!"), ("input", "IssueUpdateInput!")],
+            &items,
+        )
+        .await
+    }
+
+    /// Archive several issues, batched
+    pub async fn archive_issues(&self, ids: &[String]) -> Result<Vec<Result<(), String>>> {
+        let items: Vec<Vec<serde_json::Value>> = ids.iter().map(|id| vec![json!(id)]).collect();
+        self.batch_mutation("issueArchive", &[("id", "String!")], &items)
+            .await
+    }
+
+    /// Upcoming and current cycles of a team
+    pub async fn get_cycles(&self, team_id: &str) -> Result<Vec<CycleOption>> {
+        const QUERY: &str = r#"
+            query TeamCycles($teamId: String!) {
+                team(id: $teamId) {
+                    cycles(filter: { isPast: { eq: false } }) {
+                        nodes {
+                            id
+                            number
+                            name
+                        }
+                    }
+                }
+            }
+        "#;
+
+        let variables = json!({ "teamId": team_id });
+        let data: TeamCyclesData = self.query(QUERY, Some(variables)).await?;
+
+        let mut cycles = data.team.cycles.nodes;
+        cycles.sort_by_key(|c| c.number);
+        Ok(cycles)
+    }
+
+    pub async fn get_custom_views(&self) -> Result<Vec<CustomView>> {
+        const QUERY: &str = r#"
+            query CustomViews {
+                customViews(first: 100) {
+                    nodes {
+                        name
+                        description
+                        filterData
+                    }
+                }
+            }
+        "#;
+
+        let data: CustomViewsData = self.query(QUERY, None).await?;
+        Ok(data.custom_views.nodes)
+    }
+
+    /// Fetch every issue matching an `IssueFilter`, following pagination
+    pub async fn get_filtered_issues(
+        &self,
+        filter: &serde_json::Value,
+    ) -> Result<Vec<IssueSummary>> {
+        const QUERY: &str = concat!(
+            r#"
+            query FilteredIssues($filter: IssueFilter, $after: String) {
+                issues(first: 100, after: $after, filter: $filter) {
+                    nodes {
+                        ...IssueSummaryFields
+                    }
+                    pageInfo {
+                        hasNextPage
+                        endCursor
+                    }
+                }
+            }
+        "#,
+            issue_summary_fragment!()
+        );
+
+        let mut issues = Vec::new();
+        let mut after: Option<String> = None;
+
+        loop {
+            let variables = json!({ "filter": filter, "after": after });
+            let data: IssuesData = self.query(QUERY, Some(variables)).await?;
+            let connection = data.issues;
+
+            issues.extend(connection.nodes);
+
+            match connection.page_info.end_cursor {
+                Some(cursor) if connection.page_info.has_next_page => after = Some(cursor),
+                _ => break,
+            }
+        }
+
+        Ok(issues)
+    }
+
+    pub async fn get_active_cycle(&self, team_id: &str) -> Result<Option<ActiveCycle>> {
+        const QUERY: &str = concat!(
+            r#"
+            query ActiveCycle($teamId: String!) {
+                team(id: $teamId) {
+                    activeCycle {
+                        number
+                        name
+                        endsAt
+                        progress
+                        issues(first: 250) {
+                            nodes {
+                                ...IssueSummaryFields
+                            }
+                        }
+                    }
+                }
+            }
+        "#,
+            issue_summary_fragment!()
+        );
+
+        let variables = json!({ "teamId": team_id });
+        let data: ActiveCycleData = self.query(QUERY, Some(variables)).await?;
+        Ok(data.team.active_cycle)
+    }
+
+    /// Record that `issue_id` duplicates `duplicate_of_id`
+    pub async fn mark_duplicate(&self, issue_id: &str, duplicate_of_id: &str) -> Result<()> {
+        const MUTATION: &str = r#"
+            mutation MarkDuplicate($issueId: String!, $relatedIssueId: String!) {
+                issueRelationCreate(
+                    input: { issueId: $issueId, relatedIssueId: $relatedIssueId, type: duplicate }
+                ) {
+                    success
+                }
+            }
+        "#;
+
+        let variables = json!({
+            "issueId": issue_id,
+            "relatedIssueId": duplicate_of_id
+        });
+
+        let data: IssueRelationCreateData = self.query(MUTATION, Some(variables)).await?;
+
+        if !data.issue_relation_create.success {
+            anyhow::bail!("Failed to mark issue as duplicate");
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_bare_identifiers() {
+        assert_eq!(parse_issue_ref("ENG-123").as_deref(), Some("ENG-123"));
+        assert_eq!(parse_issue_ref("  ENG-123\n").as_deref(), Some("ENG-123"));
+        assert_eq!(parse_issue_ref("A1-7").as_deref(), Some("A1-7"));
+    }
+
+    #[test]
+    fn normalizes_lowercase_keys() {
+        assert_eq!(parse_issue_ref("eng-42").as_deref(), Some("ENG-42"));
+        assert_eq!(parse_issue_ref("Eng-42").as_deref(), Some("ENG-42"));
+    }
+
+    #[test]
+    fn parses_urls() {
+        for url in [
+            "https://linear.app/acme/issue/ENG-123",
+            "https://linear.app/acme/issue/ENG-123/fix-the-login-page",
+            "https://linear.app/acme/issue/eng-123/slug?comment=abc",
+            "https://linear.app/acme/issue/ENG-123#comment-1",
+            "http://linear.app/acme/issue/ENG-123/",
+            "https://www.linear.app/acme/issue/ENG-123/slug",
+            "www.linear.app/acme/issue/ENG-123",
+            "linear.app/acme/issue/ENG-123",
+        ] {
+            assert_eq!(parse_issue_ref(url).as_deref(), Some("ENG-123"), "{}", url);
+        }
+    }
+
+    #[test]
+    fn rejects_junk() {
+        for input in [
+            "",
+            "ENG",
+            "ENG-",
+            "-123",
+            "123-456",
+            "ENG-12a",
+            "ENG_123",
+            "ENG-123 is broken",
+            "Fix ENG-123",
+            "en g-1",
+            "https://linear.app/acme/project/ENG-123",
+            "https://linear.app/acme/issue/",
+            "https://notlinear.app/acme/issue/ENG-123",
+            "https://example.com/linear.app/acme/issue/ENG-123",
+        ] {
+            assert_eq!(parse_issue_ref(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn not_found_errors() {
+        let error =
+            |json: serde_json::Value| -> GraphQLError { serde_json::from_value(json).unwrap() };
+        assert!(error(json!({
+            "message": "Could not load issue",
+            "extensions": { "code": "ENTITY_NOT_FOUND" }
+        }))
+        .is_not_found());
+        assert!(error(json!({
+            "message": "Entity not found: Issue",
+            "extensions": { "type": "entity not found", "code": "INPUT_ERROR" }
+        }))
+        .is_not_found());
+        // A code decides, whatever the message says
+        assert!(!error(json!({
+            "message": "Team not found for this key",
+            "extensions": { "code": "AUTHENTICATION_ERROR", "type": "authentication error" }
+        }))
+        .is_not_found());
+
+        // Without extensions, fall back to the message
+        assert!(error(json!({ "message": "Entity not found: Issue" })).is_not_found());
+        assert!(!error(json!({ "message": "Rate limit exceeded" })).is_not_found());
+    }
+
+    fn batch(json: serde_json::Value) -> Vec<Result<(), String>> {
+        batch_results("issueUpdate", 3, serde_json::from_value(json).unwrap())
+    }
+
+    #[test]
+    fn batch_all_succeed() {
+        let results = batch(serde_json::json!({
+            "data": {
+                "m0": { "success": true },
+                "m1": { "success": true },
+                "m2": { "success": true }
+            }
+        }));
+        assert_eq!(results, vec![Ok(()), Ok(()), Ok(())]);
+    }
+
+    #[test]
+    fn batch_errors_map_to_their_alias() {
+        let results = batch(serde_json::json!({
+            "data": { "m0": { "success": true }, "m1": null, "m2": null },
+            "errors": [
+                { "message": "Entity not found", "path": ["m1"] },
+                { "message": "Forbidden", "path": ["m2", "success"] }
+            ]
+        }));
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Err("Entity not found".to_string()),
+                Err("Forbidden".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn batch_errors_without_alias_fail_what_came_back_null() {
+        let results = batch(serde_json::json!({
+            "data": { "m0": { "success": true }, "m1": null },
+            "errors": [{ "message": "Rate limit exceeded" }]
+        }));
+        assert_eq!(
+            results,
+            vec![
+                Ok(()),
+                Err("Rate limit exceeded".to_string()),
+                Err("Rate limit exceeded".to_string())
+            ]
+        );
+
+        let results = batch(serde_json::json!({
+            "data": null,
+            "errors": [{ "message": "Variable $id1 is invalid" }]
+        }));
+        assert_eq!(
+            results,
+            vec![Err("Variable $id1 is invalid".to_string()); 3]
+        );
+    }
+
+    #[test]
+    fn batch_unsuccessful_without_errors() {
+        let results = batch(serde_json::json!({
+            "data": { "m0": { "success": false }, "m1": { "success": true } }
+        }));
+        assert_eq!(
+            results,
+            vec![
+                Err("issueUpdate failed".to_string()),
+                Ok(()),
+                Err("issueUpdate failed".to_string())
+            ]
+        );
+    }
 }
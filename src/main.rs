@@ -1,7 +1,17 @@
+mod cache;
+mod clipboard;
 mod commands;
 mod config;
+mod frecency;
+mod history;
+mod icons;
+mod keys;
+mod launcher;
 mod linear;
+mod markup;
 mod rofi;
+mod script;
+mod theme;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
@@ -10,6 +20,12 @@ use clap::{Parser, Subcommand};
 #[command(name = "rofi-linear")]
 #[command(about = "Rofi plugin for creating Linear issues")]
 struct Cli {
+    /// Launcher to prompt with, overriding the config
+    #[arg(long, global = true, value_enum)]
+    launcher: Option<launcher::Kind>,
+    /// Prompt in the terminal, same as `--launcher tty`
+    #[arg(long, global = true, conflicts_with = "launcher")]
+    tty: bool,
     #[command(subcommand)]
     command: Commands,
 }
@@ -41,12 +57,103 @@ enum Commands {
         #[arg(short, long)]
         multi_team: bool,
     },
+    /// Browse open issues assigned to you
+    Issues,
+    /// Run as a native rofi mode (used by rofi itself)
+    ///
+    /// rofi -show linear -modi "linear:rofi-linear modi --"
+    Modi {
+        /// Entry passed back by rofi
+        #[arg(allow_hyphen_values = true)]
+        entry: Option<String>,
+    },
+    /// Search issues across the workspace
+    Search {
+        /// Run as a rofi script-mode provider (used by rofi itself)
+        #[arg(long, hide = true)]
+        script: bool,
+        /// Entry passed back by rofi in script mode
+        #[arg(hide = true, allow_hyphen_values = true)]
+        entry: Option<String>,
+    },
+    /// Change an issue's workflow state
+    Status {
+        /// Issue identifier, e.g. ENG-123 (prompts if omitted)
+        issue: Option<String>,
+    },
+    /// Comment on an issue
+    Comment {
+        /// Issue identifier, e.g. ENG-123 (prompts if omitted)
+        issue: Option<String>,
+        /// Read the comment body from stdin instead of prompting
+        #[arg(long)]
+        stdin: bool,
+    },
+    /// Reassign an issue
+    Assign {
+        /// Issue identifier, e.g. ENG-123 (prompts if omitted)
+        issue: Option<String>,
+    },
+    /// Show an issue's details and actions
+    Show {
+        /// Issue identifier, e.g. ENG-123 (prompts if omitted)
+        issue: Option<String>,
+    },
+    /// Browse unread Linear notifications
+    Inbox,
+    /// Browse the issues of a saved custom view
+    View {
+        /// View name (prompts if omitted or not found)
+        name: Option<String>,
+    },
+    /// Show the team's active cycle grouped by state
+    Cycle {
+        /// Team alias to use
+        team: Option<String>,
+    },
+    /// Work through the team's triage queue one issue at a time
+    Triage {
+        /// Team alias to use
+        team: Option<String>,
+    },
+    /// Open an issue, or pick from recently created and viewed issues
+    Open {
+        /// Issue identifier or URL, e.g. ENG-123
+        issue: Option<String>,
+    },
+    /// List issues you created from the local history
+    History {
+        /// Only issues for this team alias
+        #[arg(short, long)]
+        team: Option<String>,
+        /// Only entries on or after this date (YYYY-MM-DD, today, yesterday, 7d)
+        #[arg(long)]
+        since: Option<String>,
+        /// Only entries on or before this date (YYYY-MM-DD, today, yesterday, 7d)
+        #[arg(long)]
+        until: Option<String>,
+        /// Include issues you opened, not just ones you created
+        #[arg(short, long)]
+        all: bool,
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = commands::history::Format::Text)]
+        format: commands::history::Format,
+    },
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
 
+    let config = config::load_config()?;
+    let launcher = match cli.launcher.or(config.launcher).unwrap_or_default() {
+        _ if cli.tty => launcher::Kind::Tty,
+        // Over SSH or on a console there's nowhere to open a window
+        kind if kind.graphical() && !launcher::has_display() => launcher::Kind::Tty,
+        kind => kind,
+    };
+    rofi::init(launcher, config.rofi);
+
     match cli.command {
         Commands::Auth => commands::auth::run().await,
         Commands::Link => commands::link::run().await,
@@ -58,5 +165,61 @@ async fn main() -> Result<()> {
             open_issue,
             multi_team,
         } => commands::run::run(team, quick, open_issue, multi_team).await,
+        Commands::Issues => commands::issues::run().await,
+        Commands::Modi { entry } => commands::modi::run(entry).await,
+        Commands::Search { script, entry } => {
+            if script {
+                commands::search::script(entry).await
+            } else {
+                commands::search::run().await
+            }
+        }
+        Commands::Status { issue } => commands::status::run(issue).await,
+        Commands::Comment { issue, stdin } => commands::comment::run(issue, stdin).await,
+        Commands::Assign { issue } => commands::assign::run(issue).await,
+        Commands::Show { issue } => commands::show::run(issue).await,
+        Commands::Inbox => commands::inbox::run().await,
+        Commands::View { name } => commands::view::run(name).await,
+        Commands::Cycle { team } => commands::cycle::run(team).await,
+        Commands::Triage { team } => commands::triage::run(team).await,
+        Commands::Open { issue } => commands::open::run(issue).await,
+        Commands::History {
+            team,
+            since,
+            until,
+            all,
+            format,
+        } => commands::history::run(team, since, until, all, format).await,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(args: &[&str]) -> Option<String> {
+        match Cli::try_parse_from(args).unwrap().command {
+            Commands::Search { entry, .. } | Commands::Modi { entry } => entry,
+            _ => panic!("unexpected command for {:?}", args),
+        }
+    }
+
+    #[test]
+    fn script_entries_may_start_with_a_dash() {
+        for query in ["-fix crash", "-", "--help", "--script", "--tty"] {
+            let expected = Some(query.to_string());
+            assert_eq!(
+                entry(&["rofi-linear", "search", "--script", "--", query]),
+                expected
+            );
+            assert_eq!(entry(&["rofi-linear", "modi", "--", query]), expected);
+        }
+
+        // Without the `--` a single dash still isn't taken for an option
+        assert_eq!(
+            entry(&["rofi-linear", "search", "--script", "-fix crash"]).as_deref(),
+            Some("-fix crash")
+        );
+        assert_eq!(entry(&["rofi-linear", "search", "--script", "--"]), None);
     }
 }
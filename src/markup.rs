@@ -0,0 +1,376 @@
+//! Escaping for the two little languages rofi parses: Pango markup in
+//! messages and rows, and rasi in `-theme-str`
+
+/// Escape text for use inside Pango markup
+pub fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Quote text as a rasi string literal, e.g. for a placeholder in `-theme-str`
+///
+/// Backslashes and quotes are escaped; line breaks, which a rasi string
+/// can't hold, become spaces.
+pub fn rasi_string(text: &str) -> String {
+    let mut quoted = String::with_capacity(text.len() + 2);
+    quoted.push('"');
+    for c in text.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' | '\r' => quoted.push(' '),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// Render the first `max_lines` non-empty lines of a markdown document as Pango markup
+///
+/// Only the common inline styles are kept (bold, italic, code, links become
+/// their text); block syntax is flattened to plain lines.
+pub fn from_markdown(markdown: &str, max_lines: usize) -> String {
+    let mut lines: Vec<String> = Vec::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        let trimmed = line.trim();
+
+        if trimmed.starts_with("```") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if trimmed.is_empty() {
+            continue;
+        }
+        if lines.len() == max_lines {
+            lines.push("…".to_string());
+            break;
+        }
+
+        if in_code_block {
+            lines.push(format!("<tt>{}</tt>", escape(line)));
+            continue;
+        }
+
+        let heading = trimmed.trim_start_matches('#');
+        let rendered = if heading.len() < trimmed.len() && heading.starts_with(' ') {
+            format!("<b>{}</b>", inline(heading.trim()))
+        } else if let Some(item) = trimmed
+            .strip_prefix("- ")
+            .or_else(|| trimmed.strip_prefix("* "))
+        {
+            format!("• {}", inline(item))
+        } else if let Some(quote) = trimmed.strip_prefix('>') {
+            format!("<i>{}</i>", inline(quote.trim()))
+        } else {
+            inline(trimmed)
+        };
+        lines.push(rendered);
+    }
+
+    lines.join("\n")
+}
+
+/// Convert inline markdown to Pango markup, escaping everything else
+fn inline(text: &str) -> String {
+    let chars: Vec<char> = text.chars().collect();
+    let mut out = String::new();
+    let mut open: Vec<&str> = Vec::new();
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let prev = i.checked_sub(1).map(|p| chars[p]);
+        let next = chars.get(i + 1).copied();
+
+        // `_` only counts as emphasis at word boundaries, so snake_case survives
+        let after = if next == Some(c) {
+            chars.get(i + 2).copied()
+        } else {
+            next
+        };
+        let boundary = c == '*'
+            || !(prev.is_some_and(char::is_alphanumeric)
+                && after.is_some_and(char::is_alphanumeric));
+
+        match c {
+            '`' => {
+                if let Some(end) = find(&chars, i + 1, '`') {
+                    let code: String = chars[i + 1..end].iter().collect();
+                    out.push_str(&format!("<tt>{}</tt>", escape(&code)));
+                    i = end + 1;
+                    continue;
+                }
+                out.push('`');
+            }
+            // Like italics, bold only opens before non-whitespace
+            '*' | '_'
+                if next == Some(c)
+                    && boundary
+                    && (open.contains(&"b") || after.is_some_and(|n| !n.is_whitespace())) =>
+            {
+                toggle(&mut out, &mut open, "b");
+                i += 2;
+                continue;
+            }
+            // A doubled marker that isn't bold stays literal as a pair
+            '*' | '_' if next == Some(c) => {
+                out.push(c);
+                out.push(c);
+                i += 2;
+                continue;
+            }
+            '*' | '_'
+                if boundary
+                    && (open.contains(&"i") || next.is_some_and(|n| !n.is_whitespace())) =>
+            {
+                toggle(&mut out, &mut open, "i");
+            }
+            '[' => {
+                // [text](url) keeps only the text
+                if let Some(close) = find(&chars, i + 1, ']') {
+                    if chars.get(close + 1) == Some(&'(') {
+                        if let Some(end) = find(&chars, close + 2, ')') {
+                            let label: String = chars[i + 1..close].iter().collect();
+                            out.push_str(&format!("<u>{}</u>", escape(&label)));
+                            i = end + 1;
+                            continue;
+                        }
+                    }
+                }
+                out.push('[');
+            }
+            _ => out.push_str(&escape(&c.to_string())),
+        }
+        i += 1;
+    }
+
+    // Close anything left open so the markup stays valid
+    while let Some(tag) = open.pop() {
+        out.push_str(&format!("</{}>", tag));
+    }
+    out
+}
+
+/// Open or close a tag, keeping nesting valid when emphasis overlaps
+fn toggle<'a>(out: &mut String, open: &mut Vec<&'a str>, tag: &'a str) {
+    let Some(pos) = open.iter().position(|t| *t == tag) else {
+        out.push_str(&format!("<{}>", tag));
+        open.push(tag);
+        return;
+    };
+
+    let reopen: Vec<&str> = open.drain(pos..).skip(1).collect();
+    for t in reopen.iter().rev() {
+        out.push_str(&format!("</{}>", t));
+    }
+    out.push_str(&format!("</{}>", tag));
+    for t in reopen {
+        out.push_str(&format!("<{}>", t));
+        open.push(t);
+    }
+}
+
+fn find(chars: &[char], from: usize, target: char) -> Option<usize> {
+    chars
+        .iter()
+        .skip(from)
+        .position(|&c| c == target)
+        .map(|p| p + from)
+}
+
+/// Drop Pango tags and decode entities, for launchers that show plain text
+pub fn strip(markup: &str) -> String {
+    let mut text = String::with_capacity(markup.len());
+    let mut in_tag = false;
+    for c in markup.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_ampersands_and_angle_brackets() {
+        assert_eq!(escape("R&D <team>"), "R&amp;D &lt;team&gt;");
+        assert_eq!(escape("&amp;"), "&amp;amp;");
+    }
+
+    #[test]
+    fn escape_quotes() {
+        assert_eq!(escape(r#"say "hi" it's"#), "say &quot;hi&quot; it&apos;s");
+    }
+
+    #[test]
+    fn escape_leaves_backslashes_and_newlines() {
+        assert_eq!(escape(r"C:\path"), r"C:\path");
+        assert_eq!(escape("a\nb"), "a\nb");
+    }
+
+    #[test]
+    fn escape_multiline_mesg() {
+        // -mesg takes markup, with lines joined by real newlines
+        let mesg = ["Fix <b> & \"quote\"", r"C:\temp", "last 'line'"]
+            .iter()
+            .map(|l| escape(l))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(
+            mesg,
+            "Fix &lt;b&gt; &amp; &quot;quote&quot;\nC:\\temp\nlast &apos;line&apos;"
+        );
+        assert_eq!(strip(&mesg), "Fix <b> & \"quote\"\nC:\\temp\nlast 'line'");
+    }
+
+    #[test]
+    fn rasi_string_quotes_and_backslashes() {
+        assert_eq!(rasi_string("plain"), r#""plain""#);
+        assert_eq!(rasi_string(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(rasi_string(r"C:\path\"), r#""C:\\path\\""#);
+        assert_eq!(rasi_string(r#"\""#), r#""\\\"""#);
+    }
+
+    #[test]
+    fn rasi_string_keeps_ampersands() {
+        // rasi strings aren't markup
+        assert_eq!(rasi_string("R&D <x>"), r#""R&D <x>""#);
+    }
+
+    #[test]
+    fn rasi_string_flattens_newlines() {
+        assert_eq!(rasi_string("one\ntwo"), r#""one two""#);
+        assert_eq!(rasi_string("one\r\ntwo"), r#""one  two""#);
+    }
+
+    /// Whether every tag in `markup` is closed in the order it was opened
+    fn well_formed(markup: &str) -> bool {
+        let mut open: Vec<&str> = Vec::new();
+        let mut rest = markup;
+        while let Some(start) = rest.find('<') {
+            let Some(end) = rest[start..].find('>') else {
+                return false;
+            };
+            let tag = &rest[start + 1..start + end];
+            match tag.strip_prefix('/') {
+                Some(name) if open.pop() != Some(name) => return false,
+                Some(_) => {}
+                None => open.push(tag.split(' ').next().unwrap_or(tag)),
+            }
+            rest = &rest[start + end + 1..];
+        }
+        open.is_empty()
+    }
+
+    fn md(markdown: &str) -> String {
+        let markup = from_markdown(markdown, 10);
+        assert!(well_formed(&markup), "{:?} -> {:?}", markdown, markup);
+        markup
+    }
+
+    #[test]
+    fn markdown_emphasis() {
+        assert_eq!(md("**bold** and *it*"), "<b>bold</b> and <i>it</i>");
+        assert_eq!(md("__bold__ and _it_"), "<b>bold</b> and <i>it</i>");
+    }
+
+    #[test]
+    fn markdown_nested_emphasis() {
+        assert_eq!(md("**bold _both_**"), "<b>bold <i>both</i></b>");
+        assert_eq!(md("_it **both**_"), "<i>it <b>both</b></i>");
+    }
+
+    #[test]
+    fn markdown_overlapping_emphasis_stays_nested() {
+        assert_eq!(md("**a _b** c_"), "<b>a <i>b</i></b><i> c</i>");
+        assert_eq!(md("_a **b_ c**"), "<i>a <b>b</b></i><b> c</b>");
+    }
+
+    #[test]
+    fn markdown_unclosed_emphasis_is_closed() {
+        assert_eq!(md("**bold"), "<b>bold</b>");
+        assert_eq!(md("_it"), "<i>it</i>");
+        assert_eq!(md("**bold _it"), "<b>bold <i>it</i></b>");
+    }
+
+    #[test]
+    fn markdown_stray_markers() {
+        assert_eq!(md("2 * 3 = 6"), "2 * 3 = 6");
+        assert_eq!(md("a ** b"), "a ** b");
+        assert_eq!(md("trailing *"), "trailing *");
+    }
+
+    #[test]
+    fn markdown_snake_case_survives() {
+        assert_eq!(md("rename snake_case_words"), "rename snake_case_words");
+        assert_eq!(md("my__var__name"), "my__var__name");
+        assert_eq!(md("_it_ and snake_case"), "<i>it</i> and snake_case");
+    }
+
+    #[test]
+    fn markdown_inline_code() {
+        assert_eq!(md("run `a*b*c`"), "run <tt>a*b*c</tt>");
+        assert_eq!(md("`**not bold**`"), "<tt>**not bold**</tt>");
+        assert_eq!(md("`x < y && z`"), "<tt>x &lt; y &amp;&amp; z</tt>");
+        assert_eq!(md("unclosed `tick"), "unclosed `tick");
+    }
+
+    #[test]
+    fn markdown_links_keep_their_text() {
+        assert_eq!(md("see [docs](https://x.dev/?a=1&b=2)"), "see <u>docs</u>");
+        assert_eq!(md("[R&D <team>](u)"), "<u>R&amp;D &lt;team&gt;</u>");
+        assert_eq!(md("**[bold link](u)**"), "<b><u>bold link</u></b>");
+        assert_eq!(md("[not a link] (u)"), "[not a link] (u)");
+    }
+
+    #[test]
+    fn markdown_escapes_inside_emphasis() {
+        assert_eq!(md("**a & <b>**"), "<b>a &amp; &lt;b&gt;</b>");
+        assert_eq!(
+            md("_\"q\" & 'a'_"),
+            "<i>&quot;q&quot; &amp; &apos;a&apos;</i>"
+        );
+    }
+
+    #[test]
+    fn markdown_blocks() {
+        assert_eq!(md("# Title"), "<b>Title</b>");
+        assert_eq!(md("#hashtag"), "#hashtag");
+        assert_eq!(md("- item\n* other"), "• item\n• other");
+        assert_eq!(md("> quoted"), "<i>quoted</i>");
+        assert_eq!(md("```\nlet a = b < c;\n```"), "<tt>let a = b &lt; c;</tt>");
+    }
+
+    #[test]
+    fn markdown_max_lines() {
+        let text = "one\n\ntwo\n\n\nthree\nfour";
+        assert_eq!(from_markdown(text, 2), "one\ntwo\n…");
+        assert_eq!(from_markdown(text, 4), "one\ntwo\nthree\nfour");
+        assert_eq!(from_markdown(text, 0), "…");
+        assert_eq!(from_markdown("a\n```\nb\nc\n```", 2), "a\n<tt>b</tt>\n…");
+    }
+}
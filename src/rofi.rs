@@ -1,105 +1,261 @@
-use anyhow::{Context, Result};
-use std::io::Write;
-use std::process::{Command, Stdio};
+//! Prompts and menus, shown through the configured launcher (rofi by default)
 
-/// Prompt for text input via rofi
+use anyhow::Result;
+use std::sync::OnceLock;
+
+use crate::launcher::{self, Kind, Launcher, Menu, Prompt, Selection};
+use crate::markup;
+use crate::theme::Theme;
+
+static LAUNCHER: OnceLock<(Kind, Box<dyn Launcher>)> = OnceLock::new();
+
+/// Choose the launcher backend and its theme; only the first call has an effect
+pub fn init(kind: Kind, theme: Theme) {
+    LAUNCHER.get_or_init(|| (kind, launcher::build(kind, theme)));
+}
+
+fn backend() -> &'static (Kind, Box<dyn Launcher>) {
+    LAUNCHER.get_or_init(|| (Kind::Rofi, launcher::build(Kind::Rofi, Theme::default())))
+}
+
+/// The launcher in use
+pub fn kind() -> Kind {
+    backend().0
+}
+
+fn launcher() -> &'static dyn Launcher {
+    backend().1.as_ref()
+}
+
+fn prompt_text(prompt: &Prompt) -> Result<Option<String>> {
+    Ok(launcher().input(prompt)?.filter(|t| !t.is_empty()))
+}
+
+/// Prompt for text input
 pub fn input(prompt: &str, placeholder: &str) -> Result<Option<String>> {
-    let theme_str = format!("entry {{ placeholder: \"{}\"; }}", placeholder);
-
-    let output = Command::new("rofi")
-        .args(["-dmenu", "-p", prompt, "-theme-str", &theme_str, "-l", "0"])
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
-        .output()
-        .context("Failed to run rofi")?;
-
-    if !output.status.success() {
-        return Ok(None);
+    prompt_text(&Prompt {
+        id: None,
+        prompt,
+        placeholder,
+        initial: None,
+        mesg: None,
+        suggestions: &[],
+    })
+}
+
+/// Prompt for text input, binding extra keys that also submit it
+///
+/// `suggestions` are offered as rows to pick instead of typing. Returns the
+/// text, which may be empty, and the index of the key used, `None` for Enter.
+/// Launchers without custom keys list the actions as extra rows instead;
+/// picking one submits `initial`.
+pub fn input_with_keys(
+    prompt: &str,
+    placeholder: &str,
+    initial: Option<&str>,
+    suggestions: &[String],
+    keys: &[(&str, &str)],
+) -> Result<Option<(String, Option<usize>)>> {
+    let launcher = launcher();
+    if keys.is_empty() || launcher.supports_keys() {
+        let prompt = Prompt {
+            id: None,
+            prompt,
+            placeholder,
+            initial,
+            mesg: None,
+            suggestions,
+        };
+        return launcher.input_with_keys(&prompt, keys);
     }
 
-    let text = String::from_utf8(output.stdout)
-        .context("Invalid UTF-8 from rofi")?
-        .trim()
-        .to_string();
+    let actions: Vec<String> = keys
+        .iter()
+        .map(|(_, label)| format!("» {}", label))
+        .collect();
+    let rows: Vec<String> = suggestions.iter().chain(&actions).cloned().collect();
+    let prompt = Prompt {
+        id: None,
+        prompt,
+        placeholder,
+        initial,
+        mesg: None,
+        suggestions: &rows,
+    };
 
-    if text.is_empty() {
-        Ok(None)
-    } else {
-        Ok(Some(text))
-    }
+    Ok(launcher
+        .input(&prompt)?
+        .map(|text| match actions.iter().position(|a| *a == text) {
+            Some(key) => (initial.unwrap_or_default().to_string(), Some(key)),
+            None => (text, None),
+        }))
+}
+
+/// Whether the launcher binds custom keys, rather than asking in a follow-up menu
+pub fn supports_keys() -> bool {
+    launcher().supports_keys()
 }
 
-/// Prompt for multiline text input via rofi
-pub fn input_multiline(prompt: &str, placeholder: &str) -> Result<Option<String>> {
-    let theme_str = format!("entry {{ placeholder: \"{}\"; }}", placeholder);
+/// Prompt for text input with the entry pre-filled
+pub fn input_prefilled(prompt: &str, placeholder: &str, initial: &str) -> Result<Option<String>> {
+    prompt_text(&Prompt {
+        id: None,
+        prompt,
+        placeholder,
+        initial: Some(initial),
+        mesg: None,
+        suggestions: &[],
+    })
+}
 
-    let output = Command::new("rofi")
-        .args(["-dmenu", "-p", prompt, "-theme-str", &theme_str, "-l", "0"])
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
-        .output()
-        .context("Failed to run rofi")?;
+/// Prompt for several lines of text, one line per prompt
+///
+/// The lines entered so far are shown in the message bar. Submitting an
+/// empty line finishes; cancelling discards everything. `id` keys the theme
+/// overrides, as the prompt names the issue.
+pub fn input_lines(id: &str, prompt: &str, placeholder: &str) -> Result<Option<String>> {
+    let mut lines: Vec<String> = Vec::new();
 
-    if !output.status.success() {
-        return Ok(None);
-    }
+    loop {
+        let mut mesg: Vec<String> = lines.iter().map(|l| markup::escape(l)).collect();
+        mesg.push("(submit an empty line to finish)".to_string());
+        let mesg = mesg.join("\n");
+
+        let line = launcher().input(&Prompt {
+            id: Some(id),
+            prompt,
+            placeholder,
+            initial: None,
+            mesg: Some(&mesg),
+            suggestions: &[],
+        })?;
 
-    let text = String::from_utf8(output.stdout)
-        .context("Invalid UTF-8 from rofi")?
-        .trim()
-        .to_string();
+        match line {
+            None => return Ok(None),
+            Some(line) if line.trim().is_empty() => break,
+            Some(line) => lines.push(line),
+        }
+    }
 
-    if text.is_empty() {
+    if lines.is_empty() {
         Ok(None)
     } else {
-        Ok(Some(text))
+        Ok(Some(lines.join("\n")))
     }
 }
 
-/// Select from a list of options via rofi
-pub fn select(prompt: &str, options: &[String]) -> Result<Option<usize>> {
-    let mut child = Command::new("rofi")
-        .args(["-dmenu", "-p", prompt, "-format", "i"])
-        .stdin(Stdio::piped())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::inherit())
-        .spawn()
-        .context("Failed to spawn rofi")?;
-
-    {
-        let stdin = child.stdin.as_mut().context("Failed to get stdin")?;
-        for option in options {
-            writeln!(stdin, "{}", option)?;
+/// Show a menu, emulating what the launcher can't do natively
+///
+/// Without message support the message becomes leading rows, without markup
+/// support rows are shown as plain text, and without custom keys a follow-up
+/// menu asks which action to take.
+fn pick(menu: &Menu, multi: bool) -> Result<Option<Selection>> {
+    let launcher = launcher();
+    let markup = menu.markup && launcher.supports_markup();
+
+    let header: Vec<String> = match menu.mesg {
+        Some(mesg) if !launcher.supports_message() => markup::strip(mesg)
+            .lines()
+            .map(|l| {
+                if markup {
+                    markup::escape(l)
+                } else {
+                    l.to_string()
+                }
+            })
+            .collect(),
+        _ => Vec::new(),
+    };
+    // Launchers read one row per line
+    let rows = menu.options.iter().map(|o| {
+        let row = if menu.markup && !markup {
+            markup::strip(o)
+        } else {
+            o.clone()
+        };
+        row.replace(['\n', '\r'], " ")
+    });
+    let options: Vec<String> = header.iter().cloned().chain(rows).collect();
+    let shown = Menu {
+        options: &options,
+        mesg: if header.is_empty() { menu.mesg } else { None },
+        markup,
+        icons: if header.is_empty() { menu.icons } else { &[] },
+        selected: menu.selected.map(|row| row + header.len()),
+        ..*menu
+    };
+
+    // Picking a header row just shows the menu again
+    let mut selection = loop {
+        let picked = if multi {
+            launcher.multi_select(&shown)?
+        } else {
+            launcher.select(&shown)?
+        };
+        let Some(mut selection) = picked else {
+            return Ok(None);
+        };
+
+        selection.indices.retain(|&i| i >= header.len());
+        if !selection.indices.is_empty() {
+            for i in selection.indices.iter_mut() {
+                *i -= header.len();
+            }
+            break selection;
         }
-    }
+    };
 
-    let output = child.wait_with_output().context("Failed to wait for rofi")?;
+    if !menu.keys.is_empty() && !launcher.supports_keys() {
+        let mut actions = vec!["Select".to_string()];
+        actions.extend(menu.keys.iter().map(|(_, label)| label.to_string()));
 
-    if !output.status.success() {
-        return Ok(None);
+        let action = launcher.select(&Menu {
+            id: menu.id,
+            prompt: menu.prompt,
+            options: &actions,
+            ..Default::default()
+        })?;
+        selection.custom_key = match action {
+            Some(a) if a.index() > 0 => Some(a.index() - 1),
+            Some(_) => None,
+            None => return Ok(None),
+        };
     }
 
-    let index_str = String::from_utf8(output.stdout)
-        .context("Invalid UTF-8 from rofi")?
-        .trim()
-        .to_string();
+    Ok(Some(selection))
+}
 
-    if index_str.is_empty() {
-        Ok(None)
-    } else {
-        let index: usize = index_str.parse().context("Invalid index from rofi")?;
-        Ok(Some(index))
-    }
+/// Select from a list of options
+pub fn select(prompt: &str, options: &[String]) -> Result<Option<usize>> {
+    let menu = Menu {
+        prompt,
+        options,
+        ..Default::default()
+    };
+    Ok(pick(&menu, false)?.map(|s| s.index()))
 }
 
-/// Show an error message via rofi
+/// Show an error message
 pub fn error(message: &str) -> Result<()> {
-    Command::new("rofi")
-        .args(["-e", message])
-        .status()
-        .context("Failed to show error in rofi")?;
-    Ok(())
+    launcher().error(message)
 }
 
+/// Select several options
+pub fn multi_select(prompt: &str, options: &[String]) -> Result<Option<Vec<usize>>> {
+    let menu = Menu {
+        prompt,
+        options,
+        ..Default::default()
+    };
+    Ok(pick(&menu, true)?.map(|s| s.indices))
+}
+
+/// Show a menu described in full, e.g. with markup rows or icons
+pub fn menu(menu: &Menu) -> Result<Option<Selection>> {
+    pick(menu, false)
+}
+
+/// Like `menu`, but several rows can be marked
+pub fn multi_menu(menu: &Menu) -> Result<Option<Selection>> {
+    pick(menu, true)
+}
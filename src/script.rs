@@ -0,0 +1,167 @@
+//! rofi's script-mode protocol
+//!
+//! In script mode rofi runs this binary once per step and reads the rows to
+//! show from stdout. Mode options and per-row options are sent inline, split
+//! by `\0` and `\x1f`; see rofi-script(5).
+
+/// Why rofi invoked the script, from `ROFI_RETV`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Retv {
+    /// The mode was just opened
+    Initial,
+    /// A row was picked
+    Selected,
+    /// Text that matched no row was entered
+    Custom,
+    /// `kb-custom-N` was pressed on a row
+    Key(u8),
+}
+
+pub fn retv() -> Retv {
+    parse_retv(std::env::var("ROFI_RETV").ok().as_deref())
+}
+
+fn parse_retv(value: Option<&str>) -> Retv {
+    match value.and_then(|v| v.parse::<u8>().ok()).unwrap_or(0) {
+        1 => Retv::Selected,
+        2 => Retv::Custom,
+        n if n >= 10 => Retv::Key(n - 10),
+        _ => Retv::Initial,
+    }
+}
+
+/// The `info` of the picked row, if it had one
+pub fn info() -> Option<String> {
+    std::env::var("ROFI_INFO").ok().filter(|i| !i.is_empty())
+}
+
+/// The `data` set by the previous step, if any
+pub fn data() -> Option<String> {
+    std::env::var("ROFI_DATA").ok().filter(|d| !d.is_empty())
+}
+
+/// Set a mode option such as `prompt` or `message`
+///
+/// rofi reads one option per line, so line breaks in a message are sent as
+/// carriage returns, which Pango still renders as breaks.
+pub fn option(name: &str, value: &str) {
+    println!("{}", option_line(name, value));
+}
+
+fn option_line(name: &str, value: &str) -> String {
+    format!("\0{}\x1f{}", name, clean(value, '\r'))
+}
+
+/// A row and its options
+#[derive(Default)]
+pub struct Row<'a> {
+    pub text: &'a str,
+    /// Passed back in `ROFI_INFO` when the row is picked
+    pub info: Option<&'a str>,
+    /// Icon name from the icon theme, or a path
+    pub icon: Option<&'a str>,
+    pub nonselectable: bool,
+}
+
+impl<'a> Row<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Row {
+            text,
+            ..Default::default()
+        }
+    }
+
+    pub fn info(mut self, info: &'a str) -> Self {
+        self.info = Some(info);
+        self
+    }
+
+    pub fn icon(mut self, icon: &'a str) -> Self {
+        self.icon = Some(icon);
+        self
+    }
+
+    pub fn nonselectable(mut self) -> Self {
+        self.nonselectable = true;
+        self
+    }
+
+    pub fn print(&self) {
+        println!("{}", self.line());
+    }
+
+    fn line(&self) -> String {
+        let mut options: Vec<String> = Vec::new();
+        if let Some(info) = self.info {
+            options.push(format!("info\x1f{}", clean(info, ' ')));
+        }
+        if let Some(icon) = self.icon {
+            options.push(format!("icon\x1f{}", clean(icon, ' ')));
+        }
+        if self.nonselectable {
+            options.push("nonselectable\x1ftrue".to_string());
+        }
+
+        let text = clean(self.text, ' ');
+        if options.is_empty() {
+            text
+        } else {
+            format!("{}\0{}", text, options.join("\x1f"))
+        }
+    }
+}
+
+/// Keep a value on one line and free of protocol separators
+fn clean(value: &str, newline: char) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            '\0' | '\x1f' => ' ',
+            '\n' | '\r' => newline,
+            c => c,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn retv_values() {
+        assert_eq!(parse_retv(None), Retv::Initial);
+        assert_eq!(parse_retv(Some("0")), Retv::Initial);
+        assert_eq!(parse_retv(Some("1")), Retv::Selected);
+        assert_eq!(parse_retv(Some("2")), Retv::Custom);
+        assert_eq!(parse_retv(Some("10")), Retv::Key(0));
+        assert_eq!(parse_retv(Some("13")), Retv::Key(3));
+        assert_eq!(parse_retv(Some("junk")), Retv::Initial);
+    }
+
+    #[test]
+    fn plain_row_is_just_text() {
+        assert_eq!(Row::new("Create issue").line(), "Create issue");
+    }
+
+    #[test]
+    fn row_options_follow_a_nul() {
+        let row = Row::new("ENG-1  Fix login")
+            .info("issue:abc")
+            .icon("go-next")
+            .nonselectable();
+        assert_eq!(
+            row.line(),
+            "ENG-1  Fix login\0info\x1fissue:abc\x1ficon\x1fgo-next\x1fnonselectable\x1ftrue"
+        );
+    }
+
+    #[test]
+    fn separators_are_cleaned() {
+        let row = Row::new("two\nlines\0").info("a\x1fb");
+        assert_eq!(row.line(), "two lines \0info\x1fa b");
+        assert_eq!(
+            option_line("message", "first\nsecond\x1f"),
+            "\0message\x1ffirst\rsecond "
+        );
+    }
+}
@@ -0,0 +1,212 @@
+//! rofi theming from the `rofi` section of the config
+//!
+//! ```yaml
+//! rofi:
+//!   theme: card              # a preset, a rofi theme name or a .rasi file
+//!   args: ["-font", "Inter 11"]
+//!   prompts:
+//!     title:
+//!       theme: compact
+//!     error:
+//!       args: ["-theme-str", "window { width: 400px; }"]
+//! ```
+//!
+//! Prompt overrides are keyed by the prompt's name, lowercased (`title`,
+//! `description`, `team`, ...), plus `error` for error dialogs. Prompts whose
+//! text names an issue, a team or a count pass a fixed id instead (`assign`
+//! for "Assign ENG-123", `triage` for "Triage 3/10"). An override's theme
+//! replaces the global one and its args come after the global args.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+/// Layouts shipped with rofi-linear, applied on top of the user's rofi theme
+const PRESETS: [(&str, &str); 2] = [
+    ("compact", include_str!("../assets/themes/compact.rasi")),
+    ("card", include_str!("../assets/themes/card.rasi")),
+];
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Theme {
+    /// Preset name, rofi theme name or path to a `.rasi` file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Extra arguments for every rofi call
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub prompts: HashMap<String, Style>,
+}
+
+/// Overrides for one prompt
+#[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
+pub struct Style {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+impl Theme {
+    /// Arguments styling the rofi call for the prompt with id `prompt`
+    ///
+    /// `theme_str` is rasi the caller needs, such as a placeholder; it's
+    /// merged with a preset into a single `-theme-str`.
+    pub fn args(&self, prompt: &str, theme_str: Option<&str>) -> Vec<String> {
+        let style = self.prompts.get(prompt);
+        let theme = style
+            .and_then(|s| s.theme.as_deref())
+            .or(self.theme.as_deref());
+
+        let mut args = Vec::new();
+        let mut rasi: Vec<&str> = Vec::new();
+
+        match theme.map(|t| (t, preset(t))) {
+            Some((_, Some(preset))) => rasi.push(preset),
+            Some((theme, None)) => {
+                args.push("-theme".to_string());
+                args.push(expand_home(theme));
+            }
+            None => {}
+        }
+        rasi.extend(theme_str);
+        if !rasi.is_empty() {
+            args.push("-theme-str".to_string());
+            args.push(rasi.join("\n"));
+        }
+
+        args.extend(self.args.iter().cloned());
+        if let Some(style) = style {
+            args.extend(style.args.iter().cloned());
+        }
+        args
+    }
+}
+
+fn preset(name: &str) -> Option<&'static str> {
+    PRESETS
+        .iter()
+        .find(|(preset, _)| *preset == name)
+        .map(|(_, rasi)| *rasi)
+}
+
+fn expand_home(path: &str) -> String {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().into_owned(),
+        _ => path.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::launcher::{Menu, Prompt};
+
+    fn theme() -> Theme {
+        Theme {
+            theme: Some("~/themes/nord.rasi".to_string()),
+            args: vec!["-font".to_string(), "Inter 11".to_string()],
+            prompts: HashMap::from([
+                (
+                    "assign".to_string(),
+                    Style {
+                        theme: Some("compact".to_string()),
+                        args: vec!["-lines".to_string(), "5".to_string()],
+                    },
+                ),
+                (
+                    "title".to_string(),
+                    Style {
+                        theme: None,
+                        args: vec!["-width".to_string(), "40".to_string()],
+                    },
+                ),
+            ]),
+        }
+    }
+
+    #[test]
+    fn menu_id_picks_the_override() {
+        let menu = Menu {
+            id: Some("assign"),
+            prompt: "Assign ENG-123",
+            ..Default::default()
+        };
+        assert_eq!(
+            theme().args(&menu.style_id(), None),
+            [
+                "-theme-str",
+                preset("compact").unwrap(),
+                "-font",
+                "Inter 11",
+                "-lines",
+                "5"
+            ]
+        );
+    }
+
+    #[test]
+    fn preset_merges_with_theme_str() {
+        let menu = Menu {
+            id: Some("assign"),
+            prompt: "Assign ENG-7",
+            ..Default::default()
+        };
+        let args = theme().args(&menu.style_id(), Some("entry { placeholder: \"x\"; }"));
+        assert_eq!(
+            args[..2],
+            [
+                "-theme-str".to_string(),
+                format!(
+                    "{}\nentry {{ placeholder: \"x\"; }}",
+                    preset("compact").unwrap()
+                )
+            ]
+        );
+        assert_eq!(args.iter().filter(|a| *a == "-theme-str").count(), 1);
+    }
+
+    #[test]
+    fn prompt_without_id_uses_its_lowercased_text() {
+        let prompt = Prompt {
+            id: None,
+            prompt: "Title",
+            placeholder: "",
+            initial: None,
+            mesg: None,
+            suggestions: &[],
+        };
+        let home = dirs::home_dir().unwrap().join("themes/nord.rasi");
+        assert_eq!(
+            theme().args(&prompt.style_id(), Some("x")),
+            [
+                "-theme",
+                &*home.to_string_lossy(),
+                "-theme-str",
+                "x",
+                "-font",
+                "Inter 11",
+                "-width",
+                "40"
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_ids_get_the_global_style() {
+        let menu = Menu {
+            id: Some("triage"),
+            prompt: "Triage 1/3",
+            ..Default::default()
+        };
+        let theme = Theme {
+            theme: Some("card".to_string()),
+            ..theme()
+        };
+        assert_eq!(
+            theme.args(&menu.style_id(), None),
+            ["-theme-str", preset("card").unwrap(), "-font", "Inter 11"]
+        );
+        assert!(Theme::default().args("assign", None).is_empty());
+    }
+}